@@ -0,0 +1,59 @@
+use cribbage::{
+    card::Card,
+    card_set::CardSet,
+    show_table::ShowTable,
+    the_show::{score_the_show, show_score_set},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+fn deals() -> Vec<(Vec<Card>, Card)> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut deck = CardSet::FULL.iter().collect_vec();
+    (0..1000)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            (deck[..4].to_vec(), deck[4])
+        })
+        .collect()
+}
+
+fn bench_show(c: &mut Criterion) {
+    let deals = deals();
+    let sets = deals
+        .iter()
+        .map(|(hand, starter)| (hand.iter().collect::<CardSet>(), *starter))
+        .collect_vec();
+
+    c.bench_function("score_the_show x1000", |b| {
+        b.iter(|| {
+            for (hand, starter) in &deals {
+                black_box(score_the_show(black_box(hand), black_box(starter)));
+            }
+        })
+    });
+    c.bench_function("show_score_set x1000", |b| {
+        b.iter(|| {
+            for (hand, starter) in &sets {
+                black_box(
+                    show_score_set(black_box(*hand), black_box(*starter), false)
+                        .unwrap()
+                        .total(),
+                );
+            }
+        })
+    });
+
+    let table = ShowTable::generate();
+    c.bench_function("ShowTable::score x1000", |b| {
+        b.iter(|| {
+            for (hand, starter) in &sets {
+                black_box(table.score(black_box(*hand), black_box(*starter), false));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_show);
+criterion_main!(benches);
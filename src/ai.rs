@@ -1,10 +1,13 @@
-use crate::card::{rank_from_run_order, Card, Rank, Suit};
-use crate::game::{GameAction, GameEvent};
+use crate::card::{Card, Rank};
+use crate::discard_table::DiscardTable;
+use crate::game::{GameAction, GameEvent, ScoreReason};
+use crate::rules::Rules;
 use crate::the_play::score_the_play;
+use crate::the_show::show_score_with_rules;
 use itertools::Itertools;
+use rand::{thread_rng, Rng};
 use std::sync::mpsc::{Receiver, SyncSender};
-use std::{fmt, thread, time};
-use strum::{EnumCount, IntoEnumIterator};
+use std::{cmp, fmt, thread, time};
 
 struct Combo {
     kind: ComboKind,
@@ -48,12 +51,36 @@ impl fmt::Display for ComboKind {
 }
 
 pub fn launch_ai(event_receiver: Receiver<GameEvent>, action_sender: SyncSender<GameAction>) {
+    // our seat and partner's seat when playing in partnerships
+    let mut partnership = None;
+    // the rules of the game, which say how claims are checked and whether points are avoided
+    let mut rules = Rules::default();
+    // players who have said go since the count was last reset
+    let mut said_go = Vec::new();
     loop {
         let event = event_receiver.recv();
-        thread::sleep(time::Duration::from_millis(1000));
         match event {
-            Ok(GameEvent::Deal { cards, dealer }) => {
-                let discarded = discard_cards(cards, dealer);
+            Ok(GameEvent::Seat {
+                seat,
+                partner,
+                rules: seated_rules,
+            }) => {
+                partnership = partner.map(|partner| (seat, partner));
+                rules = seated_rules;
+            }
+            Ok(GameEvent::Go { player }) => said_go.push(player),
+            Ok(GameEvent::Scored {
+                reason: ScoreReason::Go | ScoreReason::ThirtyOne | ScoreReason::LastCard,
+                ..
+            }) => said_go.clear(),
+            Ok(GameEvent::Deal {
+                cards,
+                dealer,
+                discard,
+            }) => {
+                said_go.clear();
+                thread::sleep(time::Duration::from_millis(1000));
+                let discarded = discard_cards(cards, discard, dealer, rules.lowball);
                 let Ok(_) = action_sender.send(GameAction::Discard { discarded }) else {
                     break;
                 };
@@ -63,11 +90,45 @@ pub fn launch_ai(event_receiver: Receiver<GameEvent>, action_sender: SyncSender<
                 played,
                 count,
             }) => {
-                let card = select_play(hand, played, count);
+                thread::sleep(time::Duration::from_millis(1000));
+                let partner_next = partnership.is_some_and(|(seat, partner)| {
+                    partner_plays_next(seat, partner, rules.players, &said_go)
+                });
+                let card = select_play(hand, played, count, partner_next, rules.lowball);
                 let Ok(_) = action_sender.send(GameAction::Play { card }) else {
                     break;
                 };
             }
+            Ok(GameEvent::ClaimRequest {
+                reason,
+                cards,
+                starter,
+            }) => {
+                let points = count_claim(reason, &cards, starter, &rules);
+                let Ok(_) = action_sender.send(GameAction::Claim { points }) else {
+                    break;
+                };
+            }
+            Ok(GameEvent::MugginsRequest {
+                reason,
+                cards,
+                starter,
+                claimed,
+                ..
+            }) => {
+                // take whatever was missed
+                let points = count_claim(reason, &cards, starter, &rules).saturating_sub(claimed);
+                let Ok(_) = action_sender.send(GameAction::Claim { points }) else {
+                    break;
+                };
+            }
+            Ok(GameEvent::CutRequest { min, max }) => {
+                let position = thread_rng().gen_range(min..=max);
+                let Ok(_) = action_sender.send(GameAction::Cut { position }) else {
+                    break;
+                };
+            }
+            Ok(_) => {}
             Err(_) => {
                 break;
             }
@@ -75,25 +136,53 @@ pub fn launch_ai(event_receiver: Receiver<GameEvent>, action_sender: SyncSender<
     }
 }
 
-fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
-    let mut deck = Vec::with_capacity(Rank::COUNT * Suit::COUNT - cards.len());
-    for rank in Rank::iter() {
-        for suit in Suit::iter() {
-            let card = Card::new(suit, rank);
-            if !cards.contains(&card) {
-                deck.push(card);
-            }
+/// Whether `partner` plays after `seat`, because the opponent between them has said go
+fn partner_plays_next(seat: usize, partner: usize, players: usize, said_go: &[usize]) -> bool {
+    let next = (seat + 1) % players;
+    said_go.contains(&next) && !said_go.contains(&partner)
+}
+
+/// The points there are to claim for `cards`, counted as the game will check them under `rules`
+fn count_claim(reason: ScoreReason, cards: &[Card], starter: Option<Card>, rules: &Rules) -> u8 {
+    match (reason, starter) {
+        (ScoreReason::Play, _) => score_the_play(cards),
+        (ScoreReason::Hand, Some(starter)) => {
+            show_score_with_rules(cards, &starter, false, rules).total()
+        }
+        (ScoreReason::Crib, Some(starter)) => {
+            show_score_with_rules(cards, &starter, true, rules).total()
         }
+        _ => 0,
     }
+}
+
+/// Keeps the hand expected to score the most, or the least under `lowball`
+fn discard_cards(cards: Vec<Card>, discard: usize, dealer: bool, lowball: bool) -> Vec<Card> {
+    let jokers = cards.iter().any(Card::is_joker);
+    // use the precomputed best discard when a table is available
+    if discard == 2 && !lowball && !jokers {
+        let table = DiscardTable::from_env();
+        if let Some(best) = table.and_then(|table| table.lookup(&cards, dealer)) {
+            return best.discarded.to_vec();
+        }
+    }
+
+    let deck = Card::all()
+        .filter(|card| !cards.contains(card))
+        .collect_vec();
 
     let mut cards = cards.to_owned();
-    cards.sort_by(|a, b| a.run_cmp(b));
+    cards.sort();
 
-    let hands = cards.clone().into_iter().combinations(4).collect_vec();
+    let hands = cards
+        .clone()
+        .into_iter()
+        .combinations(cards.len() - discard)
+        .collect_vec();
     // order is deterministic so we can match the discard indexes up with the hands by reversing
     let discards = cards
         .into_iter()
-        .combinations(2)
+        .combinations(discard)
         .collect_vec()
         .into_iter()
         .rev()
@@ -106,18 +195,27 @@ fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
         .map(|(i, hand)| {
             let discarded = &discards[i];
             let mut combos: Vec<Combo> = Vec::new();
-            let score = score(&hand, &discarded, &deck, dealer, &mut combos);
+            let score = score(&hand, discarded, &deck, dealer, &mut combos);
             let count_total = count_total(&hand);
             (hand, discarded, score, count_total, combos)
         })
         .collect_vec();
+    // a joker is worth more than its estimate, which leaves it out, so never throw one away
+    if jokers && !lowball {
+        results.retain(|(_, discarded, _, _, _)| !discarded.iter().any(Card::is_joker));
+    }
 
-    // choose highest scoring hand with preference to smaller count totals
+    // choose highest scoring hand, or lowest in lowball, with preference to smaller count totals
     results.sort_by(|(_, _, _, count_total_a, _), (_, _, _, count_total_b, _)| {
         count_total_b.cmp(count_total_a)
     });
     results.sort_by(|(_, _, score_a, _, _), (_, _, score_b, _, _)| {
-        score_b.partial_cmp(score_a).unwrap()
+        let order = score_b.partial_cmp(score_a).unwrap();
+        if lowball {
+            order.reverse()
+        } else {
+            order
+        }
     });
 
     // Debugging
@@ -127,8 +225,11 @@ fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
     let score = &result.2;
     let dealer_msg = if dealer { "(dealer)" } else { "(not dealer)" };
     println!(
-        "{} {} {} {} - {} {} for {} {}",
-        hand[0], hand[1], hand[2], hand[3], discarded[0], discarded[1], score, dealer_msg
+        "{} - {} for {} {}",
+        hand.iter().join(" "),
+        discarded.iter().join(" "),
+        score,
+        dealer_msg
     );
 
     let combos = &result.4;
@@ -136,8 +237,7 @@ fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
         println!("{}", combo);
     }
 
-    let discarded = results[0].1;
-    [discarded[0], discarded[1]]
+    results[0].1.to_owned()
 }
 
 fn score(
@@ -147,17 +247,8 @@ fn score(
     dealer: bool,
     combos: &mut Vec<Combo>,
 ) -> f32 {
-    let hand_score = count_fifteens(hand, deck, combos)
-        + count_pairs(hand, deck, combos)
-        + count_runs(hand, deck, combos)
-        + count_flush(hand, deck, combos)
-        + count_nobs(hand, deck, combos);
-
-    let discard_score = count_fifteens(discarded, deck, combos)
-        + count_pairs(discarded, deck, combos)
-        + count_runs(discarded, deck, combos)
-        + count_flush(discarded, deck, combos)
-        + count_nobs(discarded, deck, combos);
+    let hand_score = estimate(hand, deck, combos);
+    let discard_score = estimate(discarded, deck, combos);
 
     if dealer {
         hand_score + discard_score
@@ -166,15 +257,34 @@ fn score(
     }
 }
 
+/// The points `discarded` is expected to contribute to the crib, where `deck` holds the unseen cards
+pub fn estimate_crib(discarded: &[Card], deck: &[Card]) -> f32 {
+    estimate(discarded, deck, &mut Vec::new())
+}
+
+/// Jokers are left out, since what they're worth isn't known until the show
+fn estimate(cards: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
+    let cards = cards
+        .iter()
+        .filter(|card| !card.is_joker())
+        .copied()
+        .collect_vec();
+    if cards.is_empty() {
+        return 0f32;
+    }
+    count_fifteens(&cards, deck, combos)
+        + count_pairs(&cards, deck, combos)
+        + count_runs(&cards, deck, combos)
+        + count_flush(&cards, deck, combos)
+        + count_nobs(&cards, deck, combos)
+}
+
 fn count_total(hand: &[Card]) -> u8 {
     hand.iter().map(|card| card.count_value()).sum()
 }
 
 fn count_fifteens(cards: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
-    let card_combinations = (1..=cards.len())
-        .map(|size| cards.iter().combinations(size))
-        .into_iter()
-        .flatten();
+    let card_combinations = (1..=cards.len()).flat_map(|size| cards.iter().combinations(size));
 
     let score = card_combinations
         .map(|cards| {
@@ -208,8 +318,10 @@ fn count_pairs(cards: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
 
     let score = card_combinations
         .map(|cards| {
-            let rank = cards[0].rank();
-            if cards[1].rank() == rank {
+            let Some(rank) = cards[0].rank() else {
+                return 0f32;
+            };
+            if cards[1].rank() == Some(rank) {
                 let score = 2f32 + potential_score(filter_by_rank(deck, rank).len(), deck.len(), 4);
                 combos.push(Combo {
                     kind: ComboKind::Pair,
@@ -265,7 +377,7 @@ fn count_flush(hand: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
         return score;
     }
 
-    let score = 4f32 + potential_score(remaining_of_suit, deck.len(), 1);
+    let score = hand.len() as f32 + potential_score(remaining_of_suit, deck.len(), 1);
     combos.push(Combo {
         kind: ComboKind::Flush,
         cards: hand.to_vec(),
@@ -275,10 +387,9 @@ fn count_flush(hand: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
 }
 
 fn count_nobs(hand: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
-    return hand
-        .iter()
+    hand.iter()
         .map(|card| {
-            if card.rank() != Rank::Jack {
+            if card.rank() != Some(Rank::Jack) {
                 return 0f32;
             }
 
@@ -296,18 +407,25 @@ fn count_nobs(hand: &[Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
             });
             score
         })
-        .sum();
+        .sum()
 }
 
 fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
     let n = cards.len() as u8;
-    let start = cards[0].run_order();
+    // a joker never makes a run
+    let Some(orders) = cards
+        .iter()
+        .map(|card| card.run_order())
+        .collect::<Option<Vec<_>>>()
+    else {
+        return 0f32;
+    };
+    let start = orders[0];
 
     // bail early if any cards are off by more than 1
     let mut missing_rank: Option<Rank> = None;
-    for (i, card) in cards.iter().enumerate() {
+    for (i, &actual) in orders.iter().enumerate() {
         let expected = start + i as u8;
-        let actual = card.run_order();
         if actual < expected {
             return 0f32;
         }
@@ -317,7 +435,7 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
             }
         } else {
             if actual - expected == 1 {
-                missing_rank = Some(rank_from_run_order(expected).unwrap());
+                missing_rank = Some(Rank::try_from(expected).unwrap());
             }
             if actual - expected > 1 {
                 return 0f32;
@@ -326,12 +444,8 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
     }
 
     // potential run if we find a card of `missing_rank`
-    if missing_rank.is_some() {
-        let score = potential_score(
-            filter_by_rank(deck, missing_rank.unwrap()).len(),
-            deck.len(),
-            n,
-        );
+    if let Some(missing_rank) = missing_rank {
+        let score = potential_score(filter_by_rank(deck, missing_rank).len(), deck.len(), n);
         combos.push(Combo {
             kind: ComboKind::PotentialRun,
             cards: cards.iter().map(|card| **card).collect_vec(),
@@ -340,30 +454,30 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
         return score;
     }
 
-    let end = cards.last().unwrap().run_order();
+    let end = *orders.last().unwrap();
 
     // potential run if we find a card on either side
     if n == 2 {
         let score;
-        if cards[0].rank() == Rank::Ace {
+        if cards[0].rank() == Some(Rank::Ace) {
             score = potential_score(
-                filter_by_rank(deck, rank_from_run_order(end + 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(end + 1).unwrap()).len(),
                 deck.len(),
                 3,
             );
-        } else if cards.last().unwrap().rank() == Rank::King {
+        } else if cards.last().unwrap().rank() == Some(Rank::King) {
             score = potential_score(
-                filter_by_rank(deck, rank_from_run_order(start - 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(start - 1).unwrap()).len(),
                 deck.len(),
                 3,
             );
         } else {
             score = potential_score(
-                filter_by_rank(deck, rank_from_run_order(start - 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(start - 1).unwrap()).len(),
                 deck.len(),
                 3,
             ) + potential_score(
-                filter_by_rank(deck, rank_from_run_order(end + 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(end + 1).unwrap()).len(),
                 deck.len(),
                 3,
             );
@@ -381,22 +495,23 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
     // we score n guaranteed, a card on either side nets another point, a card within the run scores another n points
     let same_rank = cards
         .iter()
-        .map(|card| filter_by_rank(deck, card.rank()).len())
+        .filter_map(|card| card.rank())
+        .map(|rank| filter_by_rank(deck, rank).len())
         .sum();
     let score;
-    if cards[0].rank() == Rank::Ace {
+    if cards[0].rank() == Some(Rank::Ace) {
         score = n as f32
             + potential_score(same_rank, deck.len(), n)
             + potential_score(
-                filter_by_rank(deck, rank_from_run_order(end + 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(end + 1).unwrap()).len(),
                 deck.len(),
                 1,
             );
-    } else if cards.last().unwrap().rank() == Rank::King {
+    } else if cards.last().unwrap().rank() == Some(Rank::King) {
         score = n as f32
             + potential_score(same_rank, deck.len(), n)
             + potential_score(
-                filter_by_rank(deck, rank_from_run_order(start - 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(start - 1).unwrap()).len(),
                 deck.len(),
                 1,
             );
@@ -404,12 +519,12 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
         score = n as f32
             + potential_score(same_rank, deck.len(), n)
             + potential_score(
-                filter_by_rank(deck, rank_from_run_order(start - 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(start - 1).unwrap()).len(),
                 deck.len(),
                 1,
             )
             + potential_score(
-                filter_by_rank(deck, rank_from_run_order(end + 1).unwrap()).len(),
+                filter_by_rank(deck, Rank::try_from(end + 1).unwrap()).len(),
                 deck.len(),
                 1,
             );
@@ -420,14 +535,22 @@ fn count_run(cards: &[&Card], deck: &[Card], combos: &mut Vec<Combo>) -> f32 {
         cards: cards.iter().map(|card| **card).collect_vec(),
         score,
     });
-    return score;
+    score
 }
 
 fn potential_score(n_card_needed: usize, n_card_remaining: usize, potential_score: u8) -> f32 {
     n_card_needed as f32 / n_card_remaining as f32 * potential_score as f32
 }
 
-fn select_play(hand: Vec<Card>, played: Vec<Card>, count: u8) -> Card {
+/// Picks the card that pegs the most, and otherwise leaves the next player the least to score
+/// with unless `partner_next` says they're our partner. Under `lowball` it's the other way round
+fn select_play(
+    hand: Vec<Card>,
+    played: Vec<Card>,
+    count: u8,
+    partner_next: bool,
+    lowball: bool,
+) -> Card {
     let playable_cards = hand
         .into_iter()
         .filter(|card| card.count_value() + count <= 31)
@@ -435,41 +558,55 @@ fn select_play(hand: Vec<Card>, played: Vec<Card>, count: u8) -> Card {
     let mut results = playable_cards
         .iter()
         .map(|card| {
+            // a joker scores nothing, but can always be played, so hold it back for a go
+            if card.is_joker() {
+                return (card, -2);
+            }
             let mut cards = played.to_owned();
             cards.push(card.to_owned());
             let score = score_the_play(&cards);
 
             if score == 0 {
+                // an opening for the next player helps a partner and hurts an opponent
+                let opening = if partner_next { 1 } else { -1 };
                 let Some(last_card) = played.last() else {
                     return (card, 0i8);
                 };
 
-                // discourage giving the oppontent a run opportunity
-                let last_run_order = last_card.run_order();
-                let card_run_order = card.run_order();
-                let diff = last_run_order.max(card_run_order) - last_run_order.min(card_run_order);
-                if diff <= 2 {
-                    return (card, -1);
+                // a run opportunity, which a joker never gives
+                if let (Some(last_run_order), Some(card_run_order)) =
+                    (last_card.run_order(), card.run_order())
+                {
+                    if last_run_order.abs_diff(card_run_order) <= 2 {
+                        return (card, opening);
+                    }
                 }
 
                 let count = card.count_value() + count;
-                // discourage making the count 10 since holding 5s is common
+                // making the count 10, since holding 5s is common
                 if count == 10 {
-                    return (card, -1);
+                    return (card, opening);
                 }
 
-                // encourage keeping the count 11+ away from a score
-                if count < 5 || count < 21 {
+                // encourage keeping the count 11+ away from an opponent's score
+                if !partner_next && (count < 5 || count < 21) {
                     return (card, 1);
                 }
             }
 
-            return (card, score as i8);
+            (card, score as i8)
+        })
+        .map(|(card, value)| {
+            if lowball {
+                (card, -value)
+            } else {
+                (card, value)
+            }
         })
         .collect_vec();
 
     // sort by count value descending, then score value descending
-    results.sort_by(|(a, _), (b, _)| b.count_value().cmp(&a.count_value()));
+    results.sort_by_key(|(card, _)| cmp::Reverse(card.count_value()));
     results.sort_by(|(_, a), (_, b)| b.cmp(a));
 
     results[0].0.to_owned()
@@ -482,5 +619,93 @@ fn filter_by_count(deck: &[Card], count: u8) -> Vec<&Card> {
 }
 
 fn filter_by_rank(deck: &[Card], rank: Rank) -> Vec<&Card> {
-    deck.iter().filter(|card| card.rank() == rank).collect_vec()
+    deck.iter()
+        .filter(|card| card.rank() == Some(rank))
+        .collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CribFlush;
+
+    fn card(s: &str) -> Card {
+        s.parse().unwrap()
+    }
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(card).collect()
+    }
+
+    #[test]
+    fn it_claims_by_the_house_rules() {
+        let starter = "5C".parse().ok();
+        let hand = cards("5H 5D 5S JC");
+        assert_eq!(
+            count_claim(ScoreReason::Hand, &hand, starter, &Rules::default()),
+            29
+        );
+        let rules = Rules {
+            nobs: false,
+            ..Rules::default()
+        };
+        assert_eq!(count_claim(ScoreReason::Hand, &hand, starter, &rules), 28);
+
+        let crib = cards("2D 9D KD QD");
+        assert_eq!(
+            count_claim(ScoreReason::Crib, &crib, starter, &Rules::default()),
+            4
+        );
+        let rules = Rules {
+            crib_flush: CribFlush::FourCard,
+            ..Rules::default()
+        };
+        assert_eq!(count_claim(ScoreReason::Crib, &crib, starter, &rules), 8);
+    }
+
+    #[test]
+    fn it_keeps_the_worst_hand_under_lowball() {
+        let deal = cards("5H 5D 5S JC 2D 9C");
+        let fives = |kept: &[Card]| kept.iter().filter(|card| card.count_value() == 5).count();
+        for dealer in [true, false] {
+            let discarded = discard_cards(deal.clone(), 2, dealer, false);
+            let kept = deal.iter().filter(|card| !discarded.contains(card));
+            assert_eq!(fives(&kept.copied().collect_vec()), 3);
+
+            let discarded = discard_cards(deal.clone(), 2, dealer, true);
+            let kept = deal.iter().filter(|card| !discarded.contains(card));
+            assert!(fives(&kept.copied().collect_vec()) <= 1);
+        }
+    }
+
+    #[test]
+    fn it_pegs_the_least_under_lowball() {
+        let fifteen = || (cards("5D KS"), cards("JH"), 10);
+        let (hand, played, count) = fifteen();
+        assert_eq!(select_play(hand, played, count, false, false), card("5D"));
+        let (hand, played, count) = fifteen();
+        assert_eq!(select_play(hand, played, count, false, true), card("KS"));
+
+        let pair = || (cards("7D 2S"), cards("7C"), 7);
+        let (hand, played, count) = pair();
+        assert_eq!(select_play(hand, played, count, false, false), card("7D"));
+        let (hand, played, count) = pair();
+        assert_eq!(select_play(hand, played, count, false, true), card("2S"));
+    }
+
+    #[test]
+    fn it_leaves_its_partner_an_opening() {
+        assert!(!partner_plays_next(0, 2, 4, &[]));
+        assert!(partner_plays_next(0, 2, 4, &[1]));
+        assert!(!partner_plays_next(0, 2, 4, &[1, 2]));
+        assert!(partner_plays_next(3, 1, 4, &[0]));
+        assert!(!partner_plays_next(3, 1, 4, &[2]));
+
+        // the 6 makes 10 and leaves a run, which only a partner should be given
+        let opening = || (cards("6D KS"), cards("4C"), 4);
+        let (hand, played, count) = opening();
+        assert_eq!(select_play(hand, played, count, false, false), card("KS"));
+        let (hand, played, count) = opening();
+        assert_eq!(select_play(hand, played, count, true, false), card("6D"));
+    }
 }
@@ -0,0 +1,197 @@
+use crate::{ai::estimate_crib, card::Card, card_set::CardSet, the_show::show_score_set};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+
+/// The show score of a hand against every possible starter
+pub struct HandAnalysis {
+    hand: Vec<Card>,
+    starter_scores: Vec<(Card, u8)>,
+}
+
+impl HandAnalysis {
+    /// Score `hand` with each starter not in the hand or in `known`, the cards known to be out of
+    /// the deck such as your discards
+    pub fn new(hand: &[Card], known: &[Card]) -> Self {
+        let hand_set: CardSet = hand.iter().collect();
+        let seen = hand_set | known.iter().collect();
+        let starter_scores = seen
+            .complement()
+            .iter()
+            .filter_map(|starter| {
+                Some((starter, show_score_set(hand_set, starter, false)?.total()))
+            })
+            .collect_vec();
+        HandAnalysis {
+            hand: hand.to_owned(),
+            starter_scores,
+        }
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    /// Each possible starter with the hand's score
+    pub fn starter_scores(&self) -> &[(Card, u8)] {
+        &self.starter_scores
+    }
+
+    /// The starters that produce each score
+    pub fn histogram(&self) -> BTreeMap<u8, Vec<Card>> {
+        let mut histogram: BTreeMap<u8, Vec<Card>> = BTreeMap::new();
+        for (starter, score) in &self.starter_scores {
+            histogram.entry(*score).or_default().push(*starter);
+        }
+        histogram
+    }
+
+    /// How many starters produce each score
+    pub fn distribution(&self) -> BTreeMap<u8, usize> {
+        self.scores().counts().into_iter().collect()
+    }
+
+    pub fn mean(&self) -> f32 {
+        self.scores().map(f32::from).sum::<f32>() / self.starter_scores.len() as f32
+    }
+
+    pub fn variance(&self) -> f32 {
+        let mean = self.mean();
+        self.scores()
+            .map(|score| (f32::from(score) - mean).powi(2))
+            .sum::<f32>()
+            / self.starter_scores.len() as f32
+    }
+
+    pub fn min(&self) -> u8 {
+        self.scores().min().unwrap_or(0)
+    }
+
+    pub fn max(&self) -> u8 {
+        self.scores().max().unwrap_or(0)
+    }
+
+    fn scores(&self) -> impl Iterator<Item = u8> + '_ {
+        self.starter_scores.iter().map(|(_, score)| *score)
+    }
+}
+
+/// One way of splitting a dealt hand into the cards kept and the cards thrown to the crib
+pub struct DiscardOption {
+    pub hand: HandAnalysis,
+    pub discarded: Vec<Card>,
+    /// Estimated points the discards add to the crib
+    pub expected_crib: f32,
+    pub dealer: bool,
+}
+
+impl DiscardOption {
+    pub fn expected_hand(&self) -> f32 {
+        self.hand.mean()
+    }
+
+    /// The expected hand value plus the crib when dealing, or minus the crib otherwise
+    pub fn net(&self) -> f32 {
+        if self.dealer {
+            self.expected_hand() + self.expected_crib
+        } else {
+            self.expected_hand() - self.expected_crib
+        }
+    }
+}
+
+/// Evaluate every way to discard two of `cards`, best net value first
+pub fn analyze_discards(cards: &[Card], dealer: bool) -> Vec<DiscardOption> {
+    let deck = cards
+        .iter()
+        .collect::<CardSet>()
+        .complement()
+        .iter()
+        .collect_vec();
+    let mut options = cards
+        .iter()
+        .copied()
+        .combinations(2)
+        .map(|discarded| {
+            let hand = cards
+                .iter()
+                .filter(|card| !discarded.contains(card))
+                .copied()
+                .collect_vec();
+            DiscardOption {
+                hand: HandAnalysis::new(&hand, &discarded),
+                expected_crib: estimate_crib(&discarded, &deck),
+                discarded,
+                dealer,
+            }
+        })
+        .collect_vec();
+
+    options.sort_by(|a, b| b.net().total_cmp(&a.net()));
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_hand;
+
+    #[test]
+    fn it_lists_every_discard() {
+        let (cards, _) = parse_hand("2H 3C 5D 5S JH KD").unwrap();
+        let options = analyze_discards(&cards, true);
+        assert_eq!(options.len(), 15);
+        for option in &options {
+            assert_eq!(option.hand.hand().len(), 4);
+            assert_eq!(option.discarded.len(), 2);
+            assert_eq!(option.hand.starter_scores().len(), 46);
+        }
+        assert!(options.windows(2).all(|w| w[0].net() >= w[1].net()));
+    }
+
+    #[test]
+    fn it_scores_the_kept_hand_over_every_starter() {
+        let (cards, _) = parse_hand("5H 5D 5S JC 2H 3C").unwrap();
+        let options = analyze_discards(&cards, false);
+        let option = options
+            .iter()
+            .find(|option| option.discarded == parse_hand("2H 3C").unwrap().0)
+            .unwrap();
+        // the fives and jack are worth 14 alone and the last five makes 29
+        assert_eq!(option.hand.max(), 29);
+        assert_eq!(option.hand.min(), 14);
+        assert_eq!(option.hand.distribution()[&29], 1);
+    }
+
+    #[test]
+    fn it_analyzes_a_hand_over_every_starter() {
+        let (hand, _) = parse_hand("2H 4C 6D 8S").unwrap();
+        let analysis = HandAnalysis::new(&hand, &[]);
+        assert_eq!(analysis.starter_scores().len(), 48);
+        assert_eq!(analysis.distribution().values().sum::<usize>(), 48);
+
+        let histogram = analysis.histogram();
+        let starters = |score| histogram.get(&score).map(Vec::len).unwrap_or(0);
+        // ten cards score nothing, pairs score 2, aces and nines make two fifteens, and threes,
+        // fives and sevens make two fifteens and a run
+        assert_eq!(starters(0), 16);
+        assert_eq!(starters(2), 12);
+        assert_eq!(starters(4), 8);
+        assert_eq!(starters(7), 12);
+        assert_eq!(histogram.keys().copied().collect_vec(), vec![0, 2, 4, 7]);
+        assert!(histogram[&7]
+            .iter()
+            .all(|card| card.run_order().unwrap() % 2 == 0));
+
+        let mean = analysis.mean();
+        let expected_mean = (12 * 2 + 8 * 4 + 12 * 7) as f32 / 48.0;
+        assert!((mean - expected_mean).abs() < 1e-5);
+        let expected_variance = histogram
+            .iter()
+            .map(|(score, starters)| starters.len() as f32 * (*score as f32 - mean).powi(2))
+            .sum::<f32>()
+            / 48.0;
+        assert!((analysis.variance() - expected_variance).abs() < 1e-4);
+        assert_eq!(analysis.min(), 0);
+        assert_eq!(analysis.max(), 7);
+    }
+}
@@ -0,0 +1,242 @@
+//! Many cribbage computations give the same answer when the suits are relabeled, since only
+//! whether cards share a suit matters. These utilities pick one representative from each set of
+//! equivalent cards so that work can be done once and mapped back.
+
+use crate::{
+    card::{Card, Suit},
+    card_set::CardSet,
+};
+use std::cmp::Reverse;
+use strum::{EnumCount, IntoEnumIterator};
+
+const SUITS: [Suit; Suit::COUNT] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// A relabeling of the four suits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SuitPermutation([Suit; Suit::COUNT]);
+
+impl SuitPermutation {
+    pub const IDENTITY: SuitPermutation = SuitPermutation(SUITS);
+
+    /// All 24 relabelings
+    pub fn all() -> impl Iterator<Item = SuitPermutation> {
+        (0..24).map(|mut n| {
+            let mut remaining = SUITS.to_vec();
+            let mut suits = SUITS;
+            for (i, suit) in suits.iter_mut().enumerate() {
+                let k = Suit::COUNT - i;
+                *suit = remaining.remove(n % k);
+                n /= k;
+            }
+            SuitPermutation(suits)
+        })
+    }
+
+    pub fn apply(&self, suit: Suit) -> Suit {
+        self.0[suit as usize]
+    }
+
+    /// Jokers have no suit, so are left as they are
+    pub fn apply_card(&self, card: Card) -> Card {
+        match card {
+            Card::Standard { rank, suit } => Card::new(self.apply(suit), rank),
+            joker => joker,
+        }
+    }
+
+    pub fn apply_set(&self, cards: CardSet) -> CardSet {
+        cards.iter().map(|card| self.apply_card(card)).collect()
+    }
+
+    /// The relabeling that undoes this one
+    pub fn inverse(&self) -> SuitPermutation {
+        let mut suits = SUITS;
+        for suit in Suit::iter() {
+            suits[self.apply(suit) as usize] = suit;
+        }
+        SuitPermutation(suits)
+    }
+
+    /// Maps the suits in `order` to clubs, diamonds, hearts and spades in turn
+    fn from_order(order: [Suit; Suit::COUNT]) -> SuitPermutation {
+        let mut suits = SUITS;
+        for (new, old) in SUITS.into_iter().zip(order) {
+            suits[old as usize] = new;
+        }
+        SuitPermutation(suits)
+    }
+}
+
+/// Relabel the suits of `cards` so that the suit masks are in descending order, returning the
+/// representative and the relabeling applied to get there
+///
+/// Two sets of cards are equivalent under relabeling suits exactly when their representatives
+/// are equal. Apply the inverse relabeling to map results about the representative back.
+pub fn canonicalize(cards: CardSet) -> (CardSet, SuitPermutation) {
+    let mut order = SUITS;
+    order.sort_by_key(|suit| Reverse(cards.suit_mask(*suit)));
+    let suits = SuitPermutation::from_order(order);
+    (suits.apply_set(cards), suits)
+}
+
+/// Like `canonicalize`, but also keeps track of the starter, which is distinguished from the
+/// hand for nobs and flushes
+pub fn canonicalize_with_starter(hand: CardSet, starter: Card) -> (CardSet, Card, SuitPermutation) {
+    let mut order = SUITS;
+    order.sort_by_key(|suit| {
+        (
+            Reverse(hand.suit_mask(*suit)),
+            Some(*suit) != starter.suit(),
+        )
+    });
+    let suits = SuitPermutation::from_order(order);
+    (suits.apply_set(hand), suits.apply_card(starter), suits)
+}
+
+/// Every representative set of `size` cards, each with the number of sets it stands for
+///
+/// The multiplicities sum to 52 choose `size`. For six cards there are about a million
+/// representatives, where there are over 20 million deals.
+pub fn canonical_deals(size: u32) -> Vec<(CardSet, u32)> {
+    let mut masks_by_len = vec![Vec::new(); 14];
+    for mask in 0..1u16 << 13 {
+        masks_by_len[mask.count_ones() as usize].push(mask);
+    }
+
+    let mut deals = Vec::new();
+    let mut masks = Vec::with_capacity(Suit::COUNT);
+    push_canonical_deals(&masks_by_len, &mut masks, size, &mut deals);
+    deals
+}
+
+/// Extend `masks` with descending suit masks holding `remaining` more cards in total
+fn push_canonical_deals(
+    masks_by_len: &[Vec<u16>],
+    masks: &mut Vec<u16>,
+    remaining: u32,
+    deals: &mut Vec<(CardSet, u32)>,
+) {
+    if masks.len() == Suit::COUNT {
+        if remaining == 0 {
+            deals.push((from_masks(masks), multiplicity(masks)));
+        }
+        return;
+    }
+
+    let max = masks.last().copied().unwrap_or(u16::MAX);
+    let lens = if masks.len() == Suit::COUNT - 1 {
+        remaining..=remaining
+    } else {
+        0..=remaining
+    };
+    for len in lens {
+        for &mask in masks_by_len[len as usize]
+            .iter()
+            .take_while(|mask| **mask <= max)
+        {
+            masks.push(mask);
+            push_canonical_deals(masks_by_len, masks, remaining - len, deals);
+            masks.pop();
+        }
+    }
+}
+
+fn from_masks(masks: &[u16]) -> CardSet {
+    let bits = masks
+        .iter()
+        .enumerate()
+        .map(|(suit, mask)| (*mask as u64) << (suit * 13))
+        .sum();
+    CardSet::from_bits(bits).unwrap()
+}
+
+/// How many distinct relabelings of the suits there are, 24 divided by the ways to shuffle
+/// identical suits among themselves
+fn multiplicity(masks: &[u16]) -> u32 {
+    let mut duplicates = 1;
+    let mut run = 1;
+    for i in 1..masks.len() {
+        if masks[i] == masks[i - 1] {
+            run += 1;
+            duplicates *= run;
+        } else {
+            run = 1;
+        }
+    }
+    24 / duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_hand;
+    use itertools::Itertools;
+    use std::collections::HashSet;
+
+    fn set(cards: &str) -> CardSet {
+        parse_hand(cards).unwrap().0.iter().collect()
+    }
+
+    #[test]
+    fn it_lists_every_permutation() {
+        let permutations: HashSet<_> = SuitPermutation::all().collect();
+        assert_eq!(permutations.len(), 24);
+        assert!(permutations.contains(&SuitPermutation::IDENTITY));
+        for suits in permutations {
+            assert_eq!(suits.inverse().inverse(), suits);
+            for suit in Suit::iter() {
+                assert_eq!(suits.inverse().apply(suits.apply(suit)), suit);
+            }
+        }
+    }
+
+    #[test]
+    fn it_canonicalizes_equivalent_hands_the_same() {
+        let hand = set("2H 3C 5D 5S JH KD");
+        let (canonical, suits) = canonicalize(hand);
+        assert_eq!(suits.inverse().apply_set(canonical), hand);
+        for relabeling in SuitPermutation::all() {
+            assert_eq!(canonicalize(relabeling.apply_set(hand)).0, canonical);
+        }
+        assert_ne!(canonicalize(set("2H 3C 5D 5S JH KH")).0, canonical);
+    }
+
+    #[test]
+    fn it_canonicalizes_the_starter_with_the_hand() {
+        let hand = set("5H 5D 5S JC");
+        let starter = "5C".parse().unwrap();
+        let (canonical, canonical_starter, suits) = canonicalize_with_starter(hand, starter);
+        assert_eq!(suits.inverse().apply_set(canonical), hand);
+        assert_eq!(suits.inverse().apply_card(canonical_starter), starter);
+        for relabeling in SuitPermutation::all() {
+            let relabeled = canonicalize_with_starter(
+                relabeling.apply_set(hand),
+                relabeling.apply_card(starter),
+            );
+            assert_eq!((relabeled.0, relabeled.1), (canonical, canonical_starter));
+        }
+
+        // nobs depends on the starter sharing the jack's suit
+        let other_starter = "5H".parse().unwrap();
+        assert_ne!(
+            canonicalize_with_starter(hand, other_starter).1,
+            canonical_starter
+        );
+    }
+
+    #[test]
+    fn it_enumerates_canonical_deals() {
+        let deals = canonical_deals(4);
+        let total: u32 = deals.iter().map(|(_, n)| n).sum();
+        assert_eq!(total, 270_725);
+        assert!(deals
+            .iter()
+            .all(|(deal, _)| deal.len() == 4 && canonicalize(*deal).0 == *deal));
+        let distinct = deals.iter().map(|(deal, _)| deal).unique().count();
+        assert_eq!(distinct, deals.len());
+
+        let deals = canonical_deals(6);
+        let total: u64 = deals.iter().map(|(_, n)| *n as u64).sum();
+        assert_eq!(total, 20_358_520);
+    }
+}
@@ -1,29 +1,146 @@
 use rand::Rng;
-use std::{cmp, fmt};
-use strum::{EnumCount, IntoEnumIterator};
+use std::{cmp, error, fmt, ops::RangeInclusive, str::FromStr};
+use strum::EnumCount;
 use strum_macros::{Display, EnumCount, EnumIter};
 
+/// Cards in a full deck
+pub const DECK_SIZE: usize = Suit::COUNT * Rank::COUNT;
+/// Cards dealt to each player
+pub const DEAL_SIZE: usize = 6;
+/// Cards in the crib, whatever the number of players
+pub const CRIB_SIZE: usize = 4;
+/// Cards that must be left on each side of a cut
+pub const CUT_MARGIN: usize = 4;
+/// Jokers a deck can be made up with
+pub const MAX_JOKERS: usize = 2;
+
+/// Serializes as its cards in the order they'll be drawn, and deserializes with the same checks
+/// as `Deck::from_cards`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<Card>", into = "Vec<Card>")
+)]
 pub struct Deck {
     cards: Vec<Card>,
 }
 
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Deck {
+    /// A full deck shuffled with the thread-local generator
     pub fn new() -> Deck {
-        let mut cards = Vec::with_capacity(Suit::COUNT * Rank::COUNT);
-        for suit in Suit::iter() {
-            for rank in Rank::iter() {
-                cards.push(Card::new(suit, rank));
+        Deck::shuffled(&mut rand::thread_rng())
+    }
+
+    /// A full deck shuffled with `rng`, so a seeded generator gives a repeatable deal
+    pub fn shuffled(rng: &mut impl Rng) -> Deck {
+        Deck::shuffled_with_jokers(0, rng)
+    }
+
+    /// A full deck with the first `jokers` of `JOKERS` added, shuffled with `rng`. Panics if
+    /// there are more than `MAX_JOKERS`
+    pub fn shuffled_with_jokers(jokers: usize, rng: &mut impl Rng) -> Deck {
+        let mut deck = Deck {
+            cards: FULL_DECK.iter().chain(&JOKERS[..jokers]).copied().collect(),
+        };
+        deck.shuffle(rng);
+        deck
+    }
+
+    /// A deck dealt in the order given, so `cards[0]` is drawn first. The cards must make up a
+    /// full deck plus the first `jokers` of `JOKERS`, as `Rules::jokers` sets for the variant
+    pub fn from_cards(mut cards: Vec<Card>, jokers: usize) -> Result<Deck, DeckError> {
+        for (i, card) in cards.iter().enumerate() {
+            if cards[..i].contains(card) {
+                return Err(DeckError::DuplicateCard(*card));
+            }
+            if card.is_joker() && !JOKERS[..jokers.min(MAX_JOKERS)].contains(card) {
+                return Err(DeckError::MissingCard(*card));
             }
         }
-        let mut deck = Deck { cards };
-        deck.shuffle();
-        deck
+        let expected = DECK_SIZE + jokers;
+        if cards.len() != expected {
+            return Err(DeckError::WrongSize {
+                expected,
+                found: cards.len(),
+            });
+        }
+
+        cards.reverse();
+        Ok(Deck { cards })
+    }
+
+    /// A full deck that deals `pone_hand` then `dealer_hand`, which must be the same size, with
+    /// `starter` turned when the pone cuts the fewest cards allowed and the rest of the cards
+    /// shuffled around it with `rng`
+    pub fn stacked(
+        dealer_hand: &[Card],
+        pone_hand: &[Card],
+        starter: Card,
+        rng: &mut impl Rng,
+    ) -> Result<Deck, DeckError> {
+        if dealer_hand.len() != pone_hand.len() {
+            return Err(DeckError::WrongHandSize {
+                expected: pone_hand.len(),
+                found: dealer_hand.len(),
+            });
+        }
+
+        let mut deal = pone_hand.to_vec();
+        deal.extend(dealer_hand);
+        Deck::stacked_deal(&deal, starter, rng)
+    }
+
+    /// A full deck that deals the cards in `deal` in order, with `starter` turned when the deck
+    /// is cut as shallow as allowed, for when `stacked` doesn't fit the game, e.g. with more
+    /// players
+    pub fn stacked_deal(
+        deal: &[Card],
+        starter: Card,
+        rng: &mut impl Rng,
+    ) -> Result<Deck, DeckError> {
+        Deck::shuffled(rng).stack(deal, starter)
+    }
+
+    /// Rearranges this deck to deal the cards in `deal` in order, with `starter` turned when the
+    /// deck is cut as shallow as allowed, keeping the rest in their order. Every card must be in
+    /// the deck, so a variant's deck is stacked by shuffling it first, e.g. with
+    /// `Deck::shuffled_with_jokers`
+    pub fn stack(self, deal: &[Card], starter: Card) -> Result<Deck, DeckError> {
+        let mut cards = deal.to_vec();
+        for (i, card) in cards.iter().chain([&starter]).enumerate() {
+            if cards[..i].contains(card) {
+                return Err(DeckError::DuplicateCard(*card));
+            }
+            if !self.cards.contains(card) {
+                return Err(DeckError::MissingCard(*card));
+            }
+        }
+
+        let mut rest = self
+            .cards
+            .into_iter()
+            .rev()
+            .filter(|card| !cards.contains(card) && *card != starter)
+            .collect::<Vec<_>>();
+        let under = rest.split_off(CUT_MARGIN.min(rest.len()));
+        cards.extend(rest);
+        cards.push(starter);
+        cards.extend(under);
+        cards.reverse();
+        Ok(Deck { cards })
     }
 
     /// Draw `n` cards from the deck
     pub fn draw_n(&mut self, n: u8) -> Result<Vec<Card>, InsufficientCardsError> {
         let remaining = self.cards.len() as u8;
-        if remaining < n.into() {
+        if remaining < n {
             return Err(InsufficientCardsError);
         }
 
@@ -34,13 +151,35 @@ impl Deck {
         Ok(cards)
     }
 
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// The number of cards that may be lifted off the top when cutting
+    pub fn cut_positions(&self) -> RangeInclusive<usize> {
+        CUT_MARGIN..=self.cards.len().saturating_sub(CUT_MARGIN)
+    }
+
+    /// Lift `position` cards off the top and put them underneath, then turn the top card.
+    /// Returns `None` if that would leave fewer than `CUT_MARGIN` cards on either side
+    pub fn cut(&mut self, position: usize) -> Option<Card> {
+        if !self.cut_positions().contains(&position) {
+            return None;
+        }
+        self.cards.rotate_right(position);
+        self.cards.pop()
+    }
+
     /// Draw a single card from the deck
     pub fn draw(&mut self) -> Result<Card, InsufficientCardsError> {
         self.cards.pop().ok_or(InsufficientCardsError)
     }
 
-    fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
+    fn shuffle(&mut self, rng: &mut impl Rng) {
         for i in 0..self.cards.len() {
             let j = rng.gen_range(i..self.cards.len());
             self.cards.swap(i, j);
@@ -48,28 +187,74 @@ impl Deck {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Card {
-    suit: Suit,
-    rank: Rank,
+/// Cards are ordered by rank and then by suit, with the jokers after every other card
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Card {
+    Standard {
+        rank: Rank,
+        suit: Suit,
+    },
+    /// Wild in the show and counts nothing in the play. A joker has no rank or suit of its own
+    Joker(Joker),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Joker {
+    Black,
+    Red,
+}
+
+/// The jokers a deck can be made up with, in the order they're added
+pub const JOKERS: [Card; MAX_JOKERS] = [Card::Joker(Joker::Black), Card::Joker(Joker::Red)];
+
+/// Every card, ordered by `Card::index`
+pub const FULL_DECK: [Card; DECK_SIZE] = {
+    let mut cards = [Card::new(Suit::Clubs, Rank::Ace); DECK_SIZE];
+    let mut i = 0;
+    while i < DECK_SIZE {
+        cards[i] = Card::new(Suit::ALL[i / Rank::COUNT], Rank::ALL[i % Rank::COUNT]);
+        i += 1;
+    }
+    cards
+};
+
 impl Card {
-    pub fn new(suit: Suit, rank: Rank) -> Self {
-        Card { suit, rank }
+    pub const fn new(suit: Suit, rank: Rank) -> Self {
+        Card::Standard { rank, suit }
+    }
+
+    /// Every card, ordered by `Card::index`
+    pub fn all() -> impl Iterator<Item = Card> {
+        FULL_DECK.into_iter()
     }
 
-    pub fn suit(&self) -> Suit {
-        self.suit
+    pub fn is_joker(&self) -> bool {
+        matches!(self, Card::Joker(_))
     }
 
-    pub fn rank(&self) -> Rank {
-        self.rank
+    /// `None` for a joker
+    pub fn suit(&self) -> Option<Suit> {
+        match self {
+            Card::Standard { suit, .. } => Some(*suit),
+            Card::Joker(_) => None,
+        }
+    }
+
+    /// `None` for a joker
+    pub fn rank(&self) -> Option<Rank> {
+        match self {
+            Card::Standard { rank, .. } => Some(*rank),
+            Card::Joker(_) => None,
+        }
     }
 
-    /// The value used when counting during the play or fifteens during the show
+    /// The value used when counting during the play or fifteens during the show. A joker counts
+    /// nothing in the play
     pub fn count_value(&self) -> u8 {
-        match self.rank {
+        let Some(rank) = self.rank() else {
+            return 0;
+        };
+        match rank {
             Rank::Ace => 1,
             Rank::Two => 2,
             Rank::Three => 3,
@@ -86,9 +271,10 @@ impl Card {
         }
     }
 
-    /// The order of a card when sorted for a run
-    pub fn run_order(&self) -> u8 {
-        match self.rank {
+    /// The order of a card when sorted for a run, or `None` for a joker
+    pub fn run_order(&self) -> Option<u8> {
+        let rank = self.rank()?;
+        Some(match rank {
             Rank::Ace => 0,
             Rank::Two => 1,
             Rank::Three => 2,
@@ -102,26 +288,91 @@ impl Card {
             Rank::Jack => 10,
             Rank::Queen => 11,
             Rank::King => 12,
+        })
+    }
+
+    /// A unique index in `0..52`, ordered by suit and then by rank. Jokers follow on from 52
+    pub fn index(&self) -> u8 {
+        match self {
+            Card::Standard { rank, suit } => *suit as u8 * Rank::COUNT as u8 + *rank as u8,
+            Card::Joker(joker) => DECK_SIZE as u8 + *joker as u8,
         }
     }
 
+    /// The card with the given `index`, or `None` if it's out of range
+    pub fn from_index(index: u8) -> Option<Card> {
+        FULL_DECK.get(usize::from(index)).copied()
+    }
+
+    /// Orders by run order, with jokers after every other card
     pub fn run_cmp(&self, other: &Self) -> cmp::Ordering {
-        if self.run_order() > other.run_order() {
-            return cmp::Ordering::Greater;
-        } else if self.run_order() < other.run_order() {
-            return cmp::Ordering::Less;
+        match (self.run_order(), other.run_order()) {
+            (Some(order), Some(other_order)) => order.cmp(&other_order),
+            (order, other_order) => other_order.is_some().cmp(&order.is_some()),
+        }
+    }
+}
+
+impl Suit {
+    /// The single-letter ASCII symbol, e.g. `H` for hearts
+    pub fn ascii_symbol(&self) -> char {
+        match self {
+            Suit::Clubs => 'C',
+            Suit::Diamonds => 'D',
+            Suit::Hearts => 'H',
+            Suit::Spades => 'S',
+        }
+    }
+}
+
+impl Rank {
+    /// The ASCII symbol, e.g. `10` for ten or `J` for jack
+    pub fn ascii_symbol(&self) -> &'static str {
+        match self {
+            Rank::Ace => "A",
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "J",
+            Rank::Queen => "Q",
+            Rank::King => "K",
+        }
+    }
+}
+
+impl Card {
+    /// The ASCII representation, e.g. `5H` or `10S`, or `BJ` and `RJ` for the black and red
+    /// jokers
+    pub fn ascii_symbol(&self) -> String {
+        match self {
+            Card::Standard { rank, suit } => {
+                format!("{}{}", rank.ascii_symbol(), suit.ascii_symbol())
+            }
+            Card::Joker(Joker::Black) => "BJ".to_string(),
+            Card::Joker(Joker::Red) => "RJ".to_string(),
         }
-        cmp::Ordering::Equal
     }
 }
 
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.rank, self.suit)
+        match self {
+            Card::Standard { rank, suit } => write!(f, "{}{}", rank, suit),
+            Card::Joker(Joker::Black) => write!(f, "🃏"),
+            Card::Joker(Joker::Red) => write!(f, "🂿"),
+        }
     }
 }
 
-#[derive(Clone, Copy, EnumCount, EnumIter, Display, PartialEq, Debug)]
+#[derive(
+    Clone, Copy, EnumCount, EnumIter, Display, PartialEq, Eq, Hash, PartialOrd, Ord, Debug,
+)]
 pub enum Suit {
     #[strum(to_string = "♣")]
     Clubs,
@@ -133,7 +384,9 @@ pub enum Suit {
     Spades,
 }
 
-#[derive(Clone, Copy, EnumCount, EnumIter, Display, PartialEq, Debug)]
+#[derive(
+    Clone, Copy, EnumCount, EnumIter, Display, PartialEq, Eq, Hash, PartialOrd, Ord, Debug,
+)]
 pub enum Rank {
     #[strum(to_string = "A")]
     Ace,
@@ -163,24 +416,656 @@ pub enum Rank {
     King,
 }
 
+impl TryFrom<Vec<Card>> for Deck {
+    type Error = DeckError;
+
+    fn try_from(cards: Vec<Card>) -> Result<Self, Self::Error> {
+        let jokers = cards.iter().filter(|card| card.is_joker()).count();
+        Deck::from_cards(cards, jokers)
+    }
+}
+
+/// The cards in the order they'll be drawn
+impl From<Deck> for Vec<Card> {
+    fn from(deck: Deck) -> Self {
+        let mut cards = deck.cards;
+        cards.reverse();
+        cards
+    }
+}
+
 #[derive(Debug)]
 pub struct InsufficientCardsError;
 
-pub fn rank_from_run_order(run_order: u8) -> Option<Rank> {
-    match run_order {
-        0 => Some(Rank::Ace),
-        1 => Some(Rank::Two),
-        2 => Some(Rank::Three),
-        3 => Some(Rank::Four),
-        4 => Some(Rank::Five),
-        5 => Some(Rank::Six),
-        6 => Some(Rank::Seven),
-        7 => Some(Rank::Eight),
-        8 => Some(Rank::Nine),
-        9 => Some(Rank::Ten),
-        10 => Some(Rank::Jack),
-        11 => Some(Rank::Queen),
-        12 => Some(Rank::King),
-        _ => None,
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeckError {
+    DuplicateCard(Card),
+    /// The card isn't in the deck for the variant
+    MissingCard(Card),
+    /// The deck doesn't have exactly `expected` cards
+    WrongSize {
+        expected: usize,
+        found: usize,
+    },
+    /// The stacked hands don't have the same number of cards
+    WrongHandSize {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckError::DuplicateCard(card) => write!(f, "duplicate card {}", card),
+            DeckError::MissingCard(card) => write!(f, "{} isn't in the deck", card),
+            DeckError::WrongSize { expected, found } => {
+                write!(
+                    f,
+                    "expected {} cards in the deck, found {}",
+                    expected, found
+                )
+            }
+            DeckError::WrongHandSize { expected, found } => {
+                write!(
+                    f,
+                    "expected {} cards in the hand, found {}",
+                    expected, found
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for DeckError {}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    /// Parses ASCII (`H`, `h`) or Unicode (`♥`) suit symbols
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "C" | "c" | "♣" | "♧" => Ok(Suit::Clubs),
+            "D" | "d" | "♦" | "♢" => Ok(Suit::Diamonds),
+            "H" | "h" | "♥" | "♡" => Ok(Suit::Hearts),
+            "S" | "s" | "♠" | "♤" => Ok(Suit::Spades),
+            "" => Err(ParseCardError::Empty),
+            other => Err(ParseCardError::InvalidSuit(other.into())),
+        }
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    /// Parses rank symbols, accepting both `T` and `10` for ten
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "A" => Ok(Rank::Ace),
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "T" | "10" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "" => Err(ParseCardError::Empty),
+            _ => Err(ParseCardError::InvalidRank(s.trim().into())),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    /// Parses a rank followed by a suit, e.g. `5S`, `TH`, `10h` or `5♠`, or a joker as `BJ`,
+    /// `RJ`, `🃏` or `🂿`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.to_ascii_uppercase().as_str() {
+            "BJ" | "🃏" => return Ok(JOKERS[0]),
+            "RJ" | "🂿" => return Ok(JOKERS[1]),
+            _ => {}
+        }
+        let Some((split, _)) = s.char_indices().last() else {
+            return Err(ParseCardError::Empty);
+        };
+        let (rank, suit) = s.split_at(split);
+        if rank.is_empty() {
+            return Err(ParseCardError::InvalidRank(rank.into()));
+        }
+        Ok(Card::new(suit.parse()?, rank.parse()?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseCardError {
+    Empty,
+    InvalidRank(String),
+    InvalidSuit(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "empty card"),
+            ParseCardError::InvalidRank(rank) => write!(f, "invalid rank {:?}", rank),
+            ParseCardError::InvalidSuit(suit) => write!(f, "invalid suit {:?}", suit),
+        }
+    }
+}
+
+impl error::Error for ParseCardError {}
+
+/// Parse a hand and optional starter separated by `|`, e.g. `"5S 5H 5D JC | 5C"`
+///
+/// Cards may be separated by whitespace or commas. Duplicate cards are rejected.
+pub fn parse_hand(s: &str) -> Result<(Vec<Card>, Option<Card>), ParseHandError> {
+    let mut sections = s.split('|');
+    let hand_section = sections.next().unwrap_or_default();
+    let starter_section = sections.next();
+    if sections.next().is_some() {
+        return Err(ParseHandError::TooManySeparators);
+    }
+
+    let tokens = |section: &'_ str| {
+        section
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+    let hand_tokens = tokens(hand_section);
+    let starter_tokens = starter_section.map(tokens).unwrap_or_default();
+    if starter_section.is_some() && starter_tokens.is_empty() {
+        return Err(ParseHandError::MissingStarter);
+    }
+    if starter_tokens.len() > 1 {
+        return Err(ParseHandError::MultipleStarters);
+    }
+
+    let mut cards: Vec<Card> = Vec::with_capacity(hand_tokens.len() + starter_tokens.len());
+    for (position, token) in hand_tokens.iter().chain(&starter_tokens).enumerate() {
+        let card = token
+            .parse()
+            .map_err(|source| ParseHandError::InvalidCard {
+                position,
+                token: token.clone(),
+                source,
+            })?;
+        if cards.contains(&card) {
+            return Err(ParseHandError::DuplicateCard(card));
+        }
+        cards.push(card);
+    }
+
+    let starter = if starter_tokens.is_empty() {
+        None
+    } else {
+        cards.pop()
+    };
+    Ok((cards, starter))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseHandError {
+    /// The card at `position` (counting from zero, starter last) couldn't be parsed
+    InvalidCard {
+        position: usize,
+        token: String,
+        source: ParseCardError,
+    },
+    DuplicateCard(Card),
+    MissingStarter,
+    MultipleStarters,
+    TooManySeparators,
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHandError::InvalidCard {
+                position,
+                token,
+                source,
+            } => write!(f, "card {} ({:?}): {}", position + 1, token, source),
+            ParseHandError::DuplicateCard(card) => write!(f, "duplicate card {}", card),
+            ParseHandError::MissingStarter => write!(f, "missing starter after '|'"),
+            ParseHandError::MultipleStarters => write!(f, "more than one starter after '|'"),
+            ParseHandError::TooManySeparators => write!(f, "more than one '|' separator"),
+        }
+    }
+}
+
+impl error::Error for ParseHandError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseHandError::InvalidCard { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Suit {
+    pub const ALL: [Suit; Suit::COUNT] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
+impl Rank {
+    /// Every rank in run order, from ace to king
+    pub const ALL: [Rank; Rank::COUNT] = [
+        Rank::Ace,
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+    ];
+}
+
+/// Converts a run order, as returned by `Card::run_order`, back to a rank
+impl TryFrom<u8> for Rank {
+    type Error = InvalidRunOrderError;
+
+    fn try_from(run_order: u8) -> Result<Self, Self::Error> {
+        Rank::ALL
+            .get(usize::from(run_order))
+            .copied()
+            .ok_or(InvalidRunOrderError(run_order))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidRunOrderError(pub u8);
+
+impl fmt::Display for InvalidRunOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no rank has run order {}", self.0)
+    }
+}
+
+impl error::Error for InvalidRunOrderError {}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{Card, Rank, Suit};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for Suit {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.ascii_symbol().encode_utf8(&mut [0; 4]))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Suit {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom)
+        }
+    }
+
+    impl Serialize for Rank {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.ascii_symbol())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rank {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom)
+        }
+    }
+
+    impl Serialize for Card {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.ascii_symbol())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Card {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use strum::IntoEnumIterator;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace()
+            .map(|card| card.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn it_orders_cards_by_rank_then_suit() {
+        let mut cards = cards("KS 5H 5C AD 10S");
+        cards.sort();
+        assert_eq!(cards, self::cards("AD 5C 5H 10S KS"));
+        assert_eq!(FULL_DECK.len(), 52);
+        assert!(Card::all().all(|card| FULL_DECK[usize::from(card.index())] == card));
+        assert_eq!(
+            Card::all().collect::<std::collections::HashSet<_>>().len(),
+            52
+        );
+    }
+
+    #[test]
+    fn it_converts_run_orders_to_ranks() {
+        for rank in Rank::iter() {
+            let card = Card::new(Suit::Spades, rank);
+            assert_eq!(Rank::try_from(card.run_order().unwrap()), Ok(rank));
+        }
+        assert_eq!(Rank::try_from(13), Err(InvalidRunOrderError(13)));
+    }
+
+    #[test]
+    fn it_deals_a_deck_in_order() {
+        let mut order = Deck::new().cards;
+        order.reverse();
+        let mut deck = Deck::from_cards(order.clone(), 0).unwrap();
+        assert_eq!(deck.draw_n(52).unwrap(), order);
+    }
+
+    #[test]
+    fn it_rejects_invalid_decks() {
+        let mut order = Deck::new().cards;
+        order.pop();
+        assert_eq!(
+            Deck::from_cards(order.clone(), 0).err(),
+            Some(DeckError::WrongSize {
+                expected: 52,
+                found: 51
+            })
+        );
+        let first = order[0];
+        order.push(first);
+        assert_eq!(
+            Deck::from_cards(order, 0).err(),
+            Some(DeckError::DuplicateCard(first))
+        );
+    }
+
+    #[test]
+    fn it_cuts_the_deck() {
+        let order = Deck::new().cards.into_iter().rev().collect::<Vec<_>>();
+        let mut deck = Deck::from_cards(order.clone(), 0).unwrap();
+        assert_eq!(deck.cut_positions(), 4..=48);
+        assert_eq!(deck.cut(3), None);
+        assert_eq!(deck.cut(49), None);
+        assert_eq!(deck.cut(10), Some(order[10]));
+        assert_eq!(deck.draw_n(41).unwrap(), order[11..]);
+        assert_eq!(deck.draw_n(10).unwrap(), order[..10]);
+    }
+
+    #[test]
+    fn it_stacks_a_deal() {
+        let dealer = cards("5H 5D 5S JC 9D 9H");
+        let pone = cards("4S 6D 7H 8C KS QS");
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut deck = Deck::stacked(&dealer, &pone, "5C".parse().unwrap(), &mut rng).unwrap();
+        assert_eq!(deck.draw_n(6).unwrap(), pone);
+        assert_eq!(deck.draw_n(6).unwrap(), dealer);
+        assert_eq!(deck.cut(CUT_MARGIN), Some("5C".parse().unwrap()));
+        assert_eq!(deck.len(), 39);
+        let rest = deck.draw_n(39).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut deck = Deck::stacked(&dealer, &pone, "5C".parse().unwrap(), &mut rng).unwrap();
+        deck.draw_n(12).unwrap();
+        deck.cut(CUT_MARGIN);
+        assert_eq!(deck.draw_n(39).unwrap(), rest);
+
+        assert_eq!(
+            Deck::stacked(&dealer, &pone, "5H".parse().unwrap(), &mut rng).err(),
+            Some(DeckError::DuplicateCard("5H".parse().unwrap()))
+        );
+        assert_eq!(
+            Deck::stacked(&dealer[..5], &pone, "5C".parse().unwrap(), &mut rng).err(),
+            Some(DeckError::WrongHandSize {
+                expected: 6,
+                found: 5
+            })
+        );
+    }
+
+    #[test]
+    fn it_stacks_a_deck_with_jokers() {
+        let deal = cards("5H 5D 5S BJ 9D 9H 4S 6D 7H 8C KS QS");
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            Deck::stacked_deal(&deal, "5C".parse().unwrap(), &mut rng).err(),
+            Some(DeckError::MissingCard(JOKERS[0]))
+        );
+        let mut deck = Deck::shuffled_with_jokers(2, &mut rng)
+            .stack(&deal, JOKERS[1])
+            .unwrap();
+        assert_eq!(deck.draw_n(12).unwrap(), deal);
+        assert_eq!(deck.cut(CUT_MARGIN), Some(JOKERS[1]));
+        assert_eq!(deck.len(), 41);
+    }
+
+    #[test]
+    fn it_makes_up_decks_with_jokers() {
+        let mut deck = Deck::shuffled_with_jokers(2, &mut rand::thread_rng());
+        assert_eq!(deck.len(), 54);
+        let mut cards = deck.draw_n(54).unwrap();
+        cards.sort();
+        assert_eq!(cards[52..], JOKERS);
+        assert!(cards[..52].iter().all(|card| !card.is_joker()));
+
+        assert_eq!(
+            Deck::from_cards(cards.clone(), 1).err(),
+            Some(DeckError::MissingCard(JOKERS[1]))
+        );
+        cards.pop();
+        assert_eq!(Deck::from_cards(cards.clone(), 1).unwrap().len(), 53);
+        assert_eq!(
+            Deck::from_cards(cards.clone(), 2).err(),
+            Some(DeckError::WrongSize {
+                expected: 54,
+                found: 53
+            })
+        );
+        cards.remove(0);
+        assert_eq!(
+            Deck::from_cards(cards, 1).err(),
+            Some(DeckError::WrongSize {
+                expected: 53,
+                found: 52
+            })
+        );
+        assert_eq!(JOKERS[0].count_value(), 0);
+        assert_eq!(
+            (JOKERS[1].rank(), JOKERS[1].suit(), JOKERS[1].run_order()),
+            (None, None, None)
+        );
+        assert_eq!(JOKERS.map(|joker| joker.index()), [52, 53]);
+    }
+
+    #[test]
+    fn it_parses_jokers() {
+        assert_eq!("BJ".parse(), Ok(JOKERS[0]));
+        assert_eq!("rj".parse(), Ok(JOKERS[1]));
+        for joker in JOKERS {
+            assert_eq!(joker.to_string().parse(), Ok(joker));
+            assert_eq!(joker.ascii_symbol().parse(), Ok(joker));
+        }
+    }
+
+    #[test]
+    fn it_formats_ascii_symbols() {
+        assert_eq!(Card::new(Suit::Hearts, Rank::Five).ascii_symbol(), "5H");
+        assert_eq!(Card::new(Suit::Spades, Rank::Ten).ascii_symbol(), "10S");
+        assert_eq!(Card::new(Suit::Clubs, Rank::Jack).ascii_symbol(), "JC");
+    }
+
+    #[test]
+    fn it_converts_cards_to_and_from_indexes() {
+        for index in 0..52 {
+            assert_eq!(Card::from_index(index).unwrap().index(), index);
+        }
+        assert_eq!(Card::from_index(52), None);
+        assert_eq!(Card::new(Suit::Clubs, Rank::Ace).index(), 0);
+        assert_eq!(Card::new(Suit::Spades, Rank::King).index(), 51);
+    }
+
+    #[test]
+    fn it_parses_ascii_cards() {
+        assert_eq!("5S".parse(), Ok(Card::new(Suit::Spades, Rank::Five)));
+        assert_eq!("TH".parse(), Ok(Card::new(Suit::Hearts, Rank::Ten)));
+        assert_eq!("10h".parse(), Ok(Card::new(Suit::Hearts, Rank::Ten)));
+        assert_eq!("JD".parse(), Ok(Card::new(Suit::Diamonds, Rank::Jack)));
+        assert_eq!(" ac ".parse(), Ok(Card::new(Suit::Clubs, Rank::Ace)));
+    }
+
+    #[test]
+    fn it_parses_what_it_displays() {
+        for card in Card::all() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+            assert_eq!(card.ascii_symbol().parse(), Ok(card));
+            let (Some(rank), Some(suit)) = (card.rank(), card.suit()) else {
+                panic!("{} has no rank or suit", card);
+            };
+            assert_eq!(rank.to_string().parse(), Ok(rank));
+            assert_eq!(suit.to_string().parse(), Ok(suit));
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_card_text() {
+        assert_eq!("".parse::<Card>(), Err(ParseCardError::Empty));
+        assert_eq!(
+            "H".parse::<Card>(),
+            Err(ParseCardError::InvalidRank("".into()))
+        );
+        assert_eq!(
+            "1H".parse::<Card>(),
+            Err(ParseCardError::InvalidRank("1".into()))
+        );
+        assert_eq!(
+            "5X".parse::<Card>(),
+            Err(ParseCardError::InvalidSuit("X".into()))
+        );
+    }
+
+    #[test]
+    fn it_parses_a_hand_and_starter() {
+        let (hand, starter) = parse_hand("5S 5H 5D JC | 5C").unwrap();
+        assert_eq!(
+            hand,
+            vec![
+                Card::new(Suit::Spades, Rank::Five),
+                Card::new(Suit::Hearts, Rank::Five),
+                Card::new(Suit::Diamonds, Rank::Five),
+                Card::new(Suit::Clubs, Rank::Jack),
+            ]
+        );
+        assert_eq!(starter, Some(Card::new(Suit::Clubs, Rank::Five)));
+
+        let (hand, starter) = parse_hand("2♥,3♥, 4♥").unwrap();
+        assert_eq!(hand.len(), 3);
+        assert_eq!(starter, None);
+    }
+
+    #[test]
+    fn it_rejects_invalid_hands() {
+        assert_eq!(
+            parse_hand("5S 5X"),
+            Err(ParseHandError::InvalidCard {
+                position: 1,
+                token: "5X".into(),
+                source: ParseCardError::InvalidSuit("X".into()),
+            })
+        );
+        assert_eq!(
+            parse_hand("5S JC | 5S"),
+            Err(ParseHandError::DuplicateCard(Card::new(
+                Suit::Spades,
+                Rank::Five
+            )))
+        );
+        assert_eq!(parse_hand("5S JC |"), Err(ParseHandError::MissingStarter));
+        assert_eq!(
+            parse_hand("5S | JC 5C"),
+            Err(ParseHandError::MultipleStarters)
+        );
+        assert_eq!(
+            parse_hand("5S | JC | 5C"),
+            Err(ParseHandError::TooManySeparators)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_serializes_cards_as_strings() {
+        let card = Card::new(Suit::Spades, Rank::Ten);
+        assert_eq!(serde_json::to_string(&card).unwrap(), "\"10S\"");
+        assert_eq!(serde_json::to_string(&Suit::Diamonds).unwrap(), "\"D\"");
+        assert_eq!(serde_json::to_string(&Rank::Queen).unwrap(), "\"Q\"");
+        assert_eq!(serde_json::to_string(&JOKERS[1]).unwrap(), "\"RJ\"");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_every_card() {
+        for suit in Suit::iter() {
+            for rank in Rank::iter() {
+                let card = Card::new(suit, rank);
+                let json = serde_json::to_string(&card).unwrap();
+                assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+                let json = serde_json::to_string(&rank).unwrap();
+                assert_eq!(serde_json::from_str::<Rank>(&json).unwrap(), rank);
+            }
+            let json = serde_json::to_string(&suit).unwrap();
+            assert_eq!(serde_json::from_str::<Suit>(&json).unwrap(), suit);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_decks() {
+        let deck = Deck::new();
+        let json = serde_json::to_string(&deck).unwrap();
+        assert_eq!(serde_json::from_str::<Deck>(&json).unwrap(), deck);
+
+        let mut cards = Vec::from(deck);
+        assert_eq!(
+            serde_json::to_string(&cards).unwrap(),
+            serde_json::to_string(&Deck::from_cards(cards.clone(), 0).unwrap()).unwrap()
+        );
+        cards[1] = cards[0];
+        let json = serde_json::to_string(&cards).unwrap();
+        assert!(serde_json::from_str::<Deck>(&json).is_err());
+        cards.pop();
+        let json = serde_json::to_string(&cards[1..]).unwrap();
+        assert!(serde_json::from_str::<Deck>(&json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_rejects_invalid_cards() {
+        for json in ["\"\"", "\"5\"", "\"1H\"", "\"5X\"", "5"] {
+            assert!(serde_json::from_str::<Card>(json).is_err(), "{}", json);
+        }
     }
 }
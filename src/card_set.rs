@@ -0,0 +1,257 @@
+use crate::card::{Card, Rank, Suit};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+/// A set of cards stored as one bit per `Card::index`. Jokers have no bit, and panic if added
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const FULL: CardSet = CardSet((1 << 52) - 1);
+
+    /// Returns `None` if any bit above the 52 cards is set
+    pub fn from_bits(bits: u64) -> Option<CardSet> {
+        if bits & !Self::FULL.0 != 0 {
+            return None;
+        }
+        Some(CardSet(bits))
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, card: Card) -> bool {
+        !card.is_joker() && self.0 & bit(card) != 0
+    }
+
+    /// Returns true if the card wasn't already in the set
+    pub fn insert(&mut self, card: Card) -> bool {
+        let inserted = !self.contains(card);
+        self.0 |= bit(card);
+        inserted
+    }
+
+    /// Returns true if the card was in the set
+    pub fn remove(&mut self, card: Card) -> bool {
+        let removed = self.contains(card);
+        if removed {
+            self.0 &= !bit(card);
+        }
+        removed
+    }
+
+    pub fn with(self, card: Card) -> CardSet {
+        CardSet(self.0 | bit(card))
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    /// Every card not in the set
+    pub fn complement(self) -> CardSet {
+        CardSet(!self.0 & Self::FULL.0)
+    }
+
+    /// The ranks held in `suit`, one bit per `Card::run_order`
+    pub fn suit_mask(self, suit: Suit) -> u16 {
+        ((self.0 >> (suit as u64 * RANKS)) & RANK_MASK) as u16
+    }
+
+    /// How many cards of each rank are in the set, indexed by `Card::run_order`
+    pub fn rank_counts(self) -> [u8; 13] {
+        let mut counts = [0; 13];
+        let mut bits = self.0;
+        while bits != 0 {
+            counts[(bits.trailing_zeros() as u64 % RANKS) as usize] += 1;
+            bits &= bits - 1;
+        }
+        counts
+    }
+
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+const RANKS: u64 = 13;
+const RANK_MASK: u64 = (1 << RANKS) - 1;
+
+/// Panics for a joker, whose index is past the 52 bits in a set
+fn bit(card: Card) -> u64 {
+    assert!(!card.is_joker(), "{} can't be in a CardSet", card);
+    1 << card.index()
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: CardSet) -> CardSet {
+        self.difference(rhs)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        self.complement()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set = CardSet::EMPTY;
+        for card in iter {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// Iterates the cards in a `CardSet` in `Card::index` order
+#[derive(Clone, Debug)]
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Card::from_index(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        CardSet(bit(card))
+    }
+}
+
+/// The jack of `suit`, used for checking nobs
+pub(crate) fn jack_of(suit: Suit) -> CardSet {
+    Card::new(suit, Rank::Jack).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_hand, JOKERS};
+    use itertools::Itertools;
+
+    #[test]
+    fn it_inserts_and_removes_cards() {
+        let five = Card::new(Suit::Hearts, Rank::Five);
+        let mut set = CardSet::EMPTY;
+        assert!(set.insert(five));
+        assert!(!set.insert(five));
+        assert!(set.contains(five));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove(five));
+        assert!(!set.remove(five));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be in a CardSet")]
+    fn it_rejects_jokers() {
+        let mut set = CardSet::EMPTY;
+        assert!(!set.contains(JOKERS[0]));
+        assert!(!set.remove(JOKERS[0]));
+        set.insert(JOKERS[0]);
+    }
+
+    #[test]
+    fn it_iterates_every_card_in_index_order() {
+        let cards = CardSet::FULL.iter().collect_vec();
+        assert_eq!(cards.len(), 52);
+        assert!(cards
+            .iter()
+            .enumerate()
+            .all(|(i, card)| card.index() as usize == i));
+        assert_eq!(cards.into_iter().collect::<CardSet>(), CardSet::FULL);
+    }
+
+    #[test]
+    fn it_combines_sets() {
+        let (a, _) = parse_hand("5H 6H 7C").unwrap();
+        let (b, _) = parse_hand("7C 8D").unwrap();
+        let a: CardSet = a.iter().collect();
+        let b: CardSet = b.iter().collect();
+        assert_eq!((a | b).len(), 4);
+        assert_eq!((a & b).iter().collect_vec(), parse_hand("7C").unwrap().0);
+        assert_eq!((a - b).len(), 2);
+        assert_eq!((!a).len(), 49);
+        assert_eq!(!!a, a);
+        assert_eq!(CardSet::from_bits(1 << 52), None);
+    }
+
+    #[test]
+    fn it_counts_suits_and_ranks() {
+        let (cards, _) = parse_hand("AH 5H 5C KS").unwrap();
+        let set: CardSet = cards.iter().collect();
+        assert_eq!(set.suit_mask(Suit::Hearts), 0b1_0001);
+        assert_eq!(set.suit_mask(Suit::Spades), 1 << 12);
+        assert_eq!(set.suit_mask(Suit::Diamonds), 0);
+        assert_eq!(set.rank_counts(), [1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+}
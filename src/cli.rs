@@ -0,0 +1,243 @@
+use cribbage::{
+    analysis::analyze_discards,
+    card::{Card, CRIB_SIZE},
+    discard_table::DiscardTable,
+    rules::Rules,
+    the_show::{wild_show_score, WildShowScore},
+};
+use itertools::Itertools;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+const DEAL_SIZE: usize = 6;
+
+/// Print the show score of `hand` with an itemized breakdown
+pub fn score(
+    hand: &[Card],
+    starter: Card,
+    is_crib: bool,
+    json: bool,
+    rules: &Rules,
+) -> Result<(), String> {
+    // each player keeps all but the cards they throw in the crib
+    let expected = if is_crib {
+        CRIB_SIZE
+    } else {
+        rules.deal_size - rules.discard_size()
+    };
+    if hand.len() != expected {
+        return Err(format!(
+            "expected {} cards in the hand, got {}",
+            expected,
+            hand.len()
+        ));
+    }
+    let mut cards = hand.to_owned();
+    cards.push(starter);
+    check_distinct(&cards)?;
+
+    let wild = wild_show_score(hand, &starter, is_crib, rules);
+    let score = wild.score;
+    if json {
+        return print_score_json(hand, starter, is_crib, &wild);
+    }
+
+    let whose = if is_crib { "crib" } else { "hand" };
+    println!("{}: {} - {}", whose, starter, hand.iter().join(" "));
+    for (joker, card) in &wild.substitutions {
+        println!("{} as {}", joker, card);
+    }
+    println!("fifteens {:>3}", score.fifteens);
+    println!("pairs    {:>3}", score.pairs);
+    println!("runs     {:>3}", score.runs);
+    println!("flush    {:>3}", score.flush);
+    println!("nobs     {:>3}", score.nobs);
+    println!("total    {:>3}", score.total());
+    Ok(())
+}
+
+/// Print every way to discard from a six card deal, best first
+pub fn discard(cards: &[Card], dealer: bool) -> Result<(), String> {
+    if cards.len() != DEAL_SIZE {
+        return Err(format!(
+            "expected {} cards dealt, got {}",
+            DEAL_SIZE,
+            cards.len()
+        ));
+    }
+    check_distinct(cards)?;
+    check_no_jokers(cards)?;
+
+    println!(
+        "{:<12} {:<6} {:>5} {:>5} {:>6} {:>3} {:>3}  distribution",
+        "keep", "throw", "hand", "crib", "net", "min", "max"
+    );
+    for option in analyze_discards(cards, dealer) {
+        println!(
+            "{:<12} {:<6} {:>5.2} {:>5.2} {:>6.2} {:>3} {:>3}  {}",
+            option.hand.hand().iter().join(" "),
+            option.discarded.iter().join(" "),
+            option.expected_hand(),
+            option.expected_crib,
+            option.net(),
+            option.hand.min(),
+            option.hand.max(),
+            option
+                .hand
+                .distribution()
+                .iter()
+                .map(|(score, starters)| format!("{}:{}", score, starters))
+                .join(" "),
+        );
+    }
+    Ok(())
+}
+
+/// Write the best discard for every deal to `path`
+pub fn generate_discard_table(path: &Path) -> Result<(), String> {
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let table = DiscardTable::generate();
+    let mut writer = BufWriter::new(file);
+    table
+        .write_to(&mut writer)
+        .and_then(|_| writer.flush())
+        .map_err(|err| err.to_string())?;
+    println!("{} deals written to {}", table.len(), path.display());
+    Ok(())
+}
+
+/// Print the best discard from a six card deal according to the table at `path`
+pub fn lookup_discard(path: &Path, cards: &[Card], dealer: bool) -> Result<(), String> {
+    if cards.len() != DEAL_SIZE {
+        return Err(format!(
+            "expected {} cards dealt, got {}",
+            DEAL_SIZE,
+            cards.len()
+        ));
+    }
+    check_distinct(cards)?;
+    check_no_jokers(cards)?;
+
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let table = DiscardTable::read_from(BufReader::new(file)).map_err(|err| err.to_string())?;
+    let best = table
+        .lookup(cards, dealer)
+        .ok_or("deal is missing from the table")?;
+    println!(
+        "throw {} {} for {:.2} net",
+        best.discarded[0], best.discarded[1], best.expected_net
+    );
+    Ok(())
+}
+
+/// The rules in the TOML file at `path`, or the standard rules without one
+pub fn load_rules(path: Option<&Path>) -> Result<Rules, String> {
+    let Some(path) = path else {
+        return Ok(Rules::default());
+    };
+
+    #[cfg(feature = "toml")]
+    return Rules::load(path).map_err(|err| format!("{}: {}", path.display(), err));
+
+    #[cfg(not(feature = "toml"))]
+    Err(format!(
+        "can't read {}: built without the `toml` feature",
+        path.display()
+    ))
+}
+
+/// A `score --json` result, with the breakdown alongside the cards
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ScoreReport<'a> {
+    hand: &'a [Card],
+    starter: Card,
+    crib: bool,
+    substitutions: &'a [(Card, Card)],
+    fifteens: u8,
+    pairs: u8,
+    runs: u8,
+    flush: u8,
+    nobs: u8,
+    total: u8,
+}
+
+#[cfg(feature = "serde")]
+fn print_score_json(
+    hand: &[Card],
+    starter: Card,
+    is_crib: bool,
+    wild: &WildShowScore,
+) -> Result<(), String> {
+    let score = wild.score;
+    let report = ScoreReport {
+        hand,
+        starter,
+        crib: is_crib,
+        substitutions: &wild.substitutions,
+        fifteens: score.fifteens,
+        pairs: score.pairs,
+        runs: score.runs,
+        flush: score.flush,
+        nobs: score.nobs,
+        total: score.total(),
+    };
+    let json = serde_json::to_string(&report).map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_score_json(_: &[Card], _: Card, _: bool, _: &WildShowScore) -> Result<(), String> {
+    Err("can't print JSON: built without the `serde` feature".to_string())
+}
+
+fn check_distinct(cards: &[Card]) -> Result<(), String> {
+    match cards.iter().duplicates().next() {
+        Some(card) => Err(format!("{} appears more than once", card)),
+        None => Ok(()),
+    }
+}
+
+/// The discard analysis covers the standard deck only
+fn check_no_jokers(cards: &[Card]) -> Result<(), String> {
+    match cards.iter().find(|card| card.is_joker()) {
+        Some(card) => Err(format!(
+            "{} can't be analyzed, jokers are only wild in the show",
+            card
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(|c| c.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn it_scores_a_hand_of_the_variant_size() {
+        let starter = "5C".parse().unwrap();
+        let hand = cards("5H 5D 5S JC");
+        assert_eq!(
+            score(&hand, starter, false, false, &Rules::default()),
+            Ok(())
+        );
+        assert_eq!(
+            score(&hand, starter, false, false, &Rules::three_player()),
+            Ok(())
+        );
+        assert_eq!(
+            score(&hand, starter, false, false, &Rules::four_player()),
+            Ok(())
+        );
+        assert!(score(&hand, starter, false, false, &Rules::five_card()).is_err());
+        assert!(score(&hand[..3], starter, false, false, &Rules::three_player()).is_err());
+    }
+}
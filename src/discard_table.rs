@@ -0,0 +1,373 @@
+use crate::{
+    canonical::{canonical_deals, canonicalize},
+    card::Card,
+    card_set::CardSet,
+    show_table::ShowTable,
+};
+use itertools::Itertools;
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    sync::OnceLock,
+    thread,
+};
+
+const MAGIC: &[u8; 8] = b"CRIBDISC";
+const VERSION: u8 = 1;
+const DEAL_SIZE: u32 = 6;
+const ENTRY_SIZE: usize = 13;
+/// Six card deals that differ by more than relabeling suits, the most entries a table can hold
+const CANONICAL_DEALS: u32 = 962_988;
+
+/// The best discard from every six card deal by estimated net points, for the dealer and for the
+/// pone
+///
+/// A discard is judged by the expected hand score over the 46 possible starters, plus the
+/// estimated crib when dealing or minus it otherwise. The hand side is exact, but the crib side
+/// is an approximation: it depends only on the ranks thrown and whether they share a suit,
+/// averaged over every two cards the opponent could throw and every starter, including cards
+/// that were dealt to this hand. Deals that differ only by relabeling suits share an entry.
+pub struct DiscardTable {
+    entries: Vec<Entry>,
+}
+
+/// The best discard from a deal and its estimated net points
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BestDiscard {
+    pub discarded: [Card; 2],
+    pub expected_net: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Entry {
+    deal: CardSet,
+    /// Index into the deal's pairs of cards for the dealer in the low four bits, the pone in the
+    /// high four bits
+    choices: u8,
+    /// Expected net points in hundredths
+    dealer_net: i16,
+    pone_net: i16,
+}
+
+impl DiscardTable {
+    /// Evaluate every six card deal, which takes about ten seconds on one core in a release build
+    pub fn generate() -> DiscardTable {
+        let deals = canonical_deals(DEAL_SIZE)
+            .into_iter()
+            .map(|(deal, _)| deal)
+            .collect_vec();
+        DiscardTable::generate_for(&deals)
+    }
+
+    /// Evaluate just `deals`, each of which must be canonical and hold six cards
+    pub fn generate_for(deals: &[CardSet]) -> DiscardTable {
+        let show_table = ShowTable::shared();
+        let crib_table = CribTable::generate(show_table);
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = deals.len().div_ceil(threads).max(1);
+
+        let mut entries = thread::scope(|scope| {
+            deals
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let crib_table = &crib_table;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|deal| evaluate(show_table, crib_table, *deal))
+                            .collect_vec()
+                    })
+                })
+                .collect_vec()
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect_vec()
+        });
+        entries.sort_by_key(|entry| entry.deal.bits());
+        DiscardTable { entries }
+    }
+
+    /// The best discard from `cards`, or `None` if they aren't six distinct standard cards in the
+    /// table
+    pub fn lookup(&self, cards: &[Card], dealer: bool) -> Option<BestDiscard> {
+        if cards.len() != DEAL_SIZE as usize || cards.iter().any(Card::is_joker) {
+            return None;
+        }
+        let deal: CardSet = cards.iter().collect();
+        if deal.len() != DEAL_SIZE {
+            return None;
+        }
+
+        let (canonical, suits) = canonicalize(deal);
+        let i = self
+            .entries
+            .binary_search_by_key(&canonical.bits(), |entry| entry.deal.bits())
+            .ok()?;
+        let entry = self.entries[i];
+        let (choice, net) = if dealer {
+            (entry.choices & 0xF, entry.dealer_net)
+        } else {
+            (entry.choices >> 4, entry.pone_net)
+        };
+        let discarded = discards(canonical).nth(choice.into())?;
+        let suits = suits.inverse();
+        Some(BestDiscard {
+            discarded: discarded.map(|card| suits.apply_card(card)),
+            expected_net: net as f32 / 100.0,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        for entry in &self.entries {
+            writer.write_all(&entry.deal.bits().to_le_bytes())?;
+            writer.write_all(&[entry.choices])?;
+            writer.write_all(&entry.dealer_net.to_le_bytes())?;
+            writer.write_all(&entry.pone_net.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(mut reader: impl Read) -> io::Result<DiscardTable> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "not a discard table");
+        let mut header = [0; 13];
+        reader.read_exact(&mut header)?;
+        if &header[..8] != MAGIC || header[8] != VERSION {
+            return Err(invalid());
+        }
+
+        let len = u32::from_le_bytes(header[9..13].try_into().unwrap());
+        if len > CANONICAL_DEALS {
+            return Err(invalid());
+        }
+        let mut entries = Vec::with_capacity(len as usize);
+        let mut bytes = [0; ENTRY_SIZE];
+        for _ in 0..len {
+            reader.read_exact(&mut bytes)?;
+            let deal = CardSet::from_bits(u64::from_le_bytes(bytes[..8].try_into().unwrap()))
+                .filter(|deal| deal.len() == DEAL_SIZE)
+                .ok_or_else(invalid)?;
+            entries.push(Entry {
+                deal,
+                choices: bytes[8],
+                dealer_net: i16::from_le_bytes([bytes[9], bytes[10]]),
+                pone_net: i16::from_le_bytes([bytes[11], bytes[12]]),
+            });
+        }
+        if !entries.is_sorted_by_key(|entry| entry.deal.bits()) {
+            return Err(invalid());
+        }
+        Ok(DiscardTable { entries })
+    }
+
+    /// The table at the path in the `CRIBBAGE_DISCARD_TABLE` environment variable, loaded once
+    ///
+    /// Returns `None` if the variable isn't set or the file can't be read.
+    pub fn from_env() -> Option<&'static DiscardTable> {
+        static TABLE: OnceLock<Option<DiscardTable>> = OnceLock::new();
+        TABLE
+            .get_or_init(|| {
+                let path = env::var_os("CRIBBAGE_DISCARD_TABLE")?;
+                let table = File::open(&path)
+                    .and_then(|file| DiscardTable::read_from(BufReader::new(file)));
+                match table {
+                    Ok(table) => Some(table),
+                    Err(err) => {
+                        eprintln!("Can't load discard table {:?}: {}", path, err);
+                        None
+                    }
+                }
+            })
+            .as_ref()
+    }
+}
+
+/// Every pair of cards in `deal`, in a fixed order that `Entry::choices` indexes into
+fn discards(deal: CardSet) -> impl Iterator<Item = [Card; 2]> {
+    deal.iter().tuple_combinations().map(|(a, b)| [a, b])
+}
+
+fn evaluate(show_table: &ShowTable, crib_table: &CribTable, deal: CardSet) -> Entry {
+    let mut best_dealer = (0, f32::MIN);
+    let mut best_pone = (0, f32::MIN);
+    for (i, discarded) in discards(deal).enumerate() {
+        let hand = deal - discarded.iter().collect();
+        let starters = !deal;
+        let total: u32 = starters
+            .iter()
+            .map(|starter| show_table.score(hand, starter, false) as u32)
+            .sum();
+        let expected_hand = total as f32 / starters.len() as f32;
+        let expected_crib = crib_table.expected(discarded);
+
+        if expected_hand + expected_crib > best_dealer.1 {
+            best_dealer = (i as u8, expected_hand + expected_crib);
+        }
+        if expected_hand - expected_crib > best_pone.1 {
+            best_pone = (i as u8, expected_hand - expected_crib);
+        }
+    }
+    Entry {
+        deal,
+        choices: best_dealer.0 | best_pone.0 << 4,
+        dealer_net: (best_dealer.1 * 100.0).round() as i16,
+        pone_net: (best_pone.1 * 100.0).round() as i16,
+    }
+}
+
+/// The average crib score given two of its cards, by rank and whether they share a suit, over
+/// every other two cards and starter without regard to the rest of the deal
+struct CribTable {
+    expected: [[[f32; 2]; 13]; 13],
+}
+
+impl CribTable {
+    fn generate(show_table: &ShowTable) -> CribTable {
+        let mut expected = [[[0.0; 2]; 13]; 13];
+        for (low, high) in (0..13u8)
+            .tuple_combinations()
+            .chain((0..13).map(|r| (r, r)))
+        {
+            for suited in [false, true] {
+                if suited && low == high {
+                    continue;
+                }
+                let first = Card::from_index(low).unwrap();
+                let second = Card::from_index(high + if suited { 0 } else { 13 }).unwrap();
+                let thrown: CardSet = [first, second].iter().collect();
+
+                let mut total = 0u64;
+                let mut count = 0u64;
+                for others in (!thrown).iter().combinations(2) {
+                    let crib = thrown | others.iter().collect();
+                    for starter in !crib {
+                        total += show_table.score(crib, starter, true) as u64;
+                        count += 1;
+                    }
+                }
+                let mean = total as f32 / count as f32;
+                expected[low as usize][high as usize][suited as usize] = mean;
+                expected[high as usize][low as usize][suited as usize] = mean;
+            }
+        }
+        CribTable { expected }
+    }
+
+    fn expected(&self, [a, b]: [Card; 2]) -> f32 {
+        let suited = a.suit() == b.suit();
+        let order = |card: Card| card.run_order().expect("no joker is in the crib table") as usize;
+        self.expected[order(a)][order(b)][suited as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canonical::SuitPermutation,
+        card::{parse_hand, JOKERS},
+    };
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    fn net(crib_table: &CribTable, deal: &[Card], discarded: [Card; 2], dealer: bool) -> f32 {
+        let show_table = ShowTable::shared();
+        let deal: CardSet = deal.iter().collect();
+        let hand = deal - discarded.iter().collect();
+        let total: u32 = (!deal)
+            .iter()
+            .map(|starter| show_table.score(hand, starter, false) as u32)
+            .sum();
+        let crib = crib_table.expected(discarded);
+        let hand = total as f32 / 46.0;
+        if dealer {
+            hand + crib
+        } else {
+            hand - crib
+        }
+    }
+
+    #[test]
+    #[ignore = "generates the whole show table, run with --release --ignored"]
+    fn it_looks_up_the_best_discard_for_any_suits() {
+        let mut rng = StdRng::seed_from_u64(34);
+        let mut deck = CardSet::FULL.iter().collect_vec();
+        let mut deals = Vec::new();
+        for _ in 0..10 {
+            deck.shuffle(&mut rng);
+            deals.push(deck[..6].to_vec());
+        }
+        let canonical = deals
+            .iter()
+            .map(|deal| canonicalize(deal.iter().collect()).0)
+            .sorted_by_key(|deal| deal.bits())
+            .dedup()
+            .collect_vec();
+        let table = DiscardTable::generate_for(&canonical);
+        let crib_table = CribTable::generate(ShowTable::shared());
+
+        for deal in &deals {
+            for suits in SuitPermutation::all().step_by(5) {
+                let deal = deal
+                    .iter()
+                    .map(|card| suits.apply_card(*card))
+                    .collect_vec();
+                for dealer in [true, false] {
+                    let best = table.lookup(&deal, dealer).unwrap();
+                    assert!(best.discarded.iter().all(|card| deal.contains(card)));
+                    let best_net = net(&crib_table, &deal, best.discarded, dealer);
+                    assert!((best_net - best.expected_net).abs() < 0.01);
+                    for (a, b) in deal.iter().copied().tuple_combinations::<(_, _)>() {
+                        assert!(net(&crib_table, &deal, [a, b], dealer) <= best_net);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(table.lookup(&deals[0][..5], true), None);
+        let mut with_joker = deals[0].clone();
+        with_joker[5] = JOKERS[0];
+        assert_eq!(table.lookup(&with_joker, true), None);
+        let (unknown, _) = parse_hand("AC 2C 3C 4C 5C 6C").unwrap();
+        if !canonical.contains(&canonicalize(unknown.iter().collect()).0) {
+            assert_eq!(table.lookup(&unknown, true), None);
+        }
+
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 13 + ENTRY_SIZE * table.len());
+        let read = DiscardTable::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(read.entries, table.entries);
+
+        bytes[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = DiscardTable::read_from(bytes.as_slice()).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn it_holds_at_most_one_entry_per_canonical_deal() {
+        assert_eq!(canonical_deals(DEAL_SIZE).len(), CANONICAL_DEALS as usize);
+    }
+
+    #[test]
+    #[ignore = "generates the whole show table, run with --release --ignored"]
+    fn it_keeps_the_fives_and_face_cards_as_dealer_and_pone() {
+        let (deal, _) = parse_hand("2H 3C 5D 5S JH KD").unwrap();
+        let canonical = canonicalize(deal.iter().collect()).0;
+        let table = DiscardTable::generate_for(&[canonical]);
+        for dealer in [true, false] {
+            let best = table.lookup(&deal, dealer).unwrap();
+            assert_eq!(best.discarded, [deal[0], deal[1]], "dealer: {}", dealer);
+        }
+    }
+}
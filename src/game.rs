@@ -1,51 +1,151 @@
 use crate::{
-    card::{Card, Deck, Rank},
+    card::{Card, Deck, Rank, CRIB_SIZE},
+    rules::{Rules, RulesError},
     the_play::score_the_play,
-    the_show::score_the_show,
+    the_show::wild_show_score,
 };
 use itertools::Itertools;
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, SeedableRng};
 use std::{
+    collections::VecDeque,
     sync::mpsc::{sync_channel, Receiver, SyncSender},
-    thread, time,
+    thread,
+    time::Duration,
 };
 
-const PLAYERS_SIZE: usize = 2;
-const CRIB_SIZE: usize = 4;
 const PLAYED_SIZE: usize = 8;
-const MAX_SCORE: u8 = 121;
 const MAX_COUNT: u8 = 31;
+const SHOW_DELAY: Duration = Duration::from_secs(2);
 
 pub struct Game {
+    rules: Rules,
     players: Vec<Player>,
     dealer_index: usize,
     player_index: usize,
+    rng: StdRng,
     deck: Deck,
+    stacked_decks: VecDeque<Deck>,
+    pinned_dealer: Option<usize>,
+    first_dealer: Option<usize>,
     crib: Vec<Card>,
     starter: Option<Card>,
     played: Vec<Card>,
+    show_delay: Duration,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Rules::default())
+    }
 }
 
 impl Game {
-    pub fn new() -> Game {
-        Game {
-            players: Vec::with_capacity(PLAYERS_SIZE),
+    /// Panics if `rules` aren't valid, see `try_new`
+    pub fn new(rules: Rules) -> Game {
+        Game::try_new(rules).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// A game with `rules`, or why they aren't valid
+    pub fn try_new(rules: Rules) -> Result<Game, RulesError> {
+        Game::with_rng(rules, StdRng::from_entropy())
+    }
+
+    /// A game whose shuffles and first dealer are determined by `seed`. Panics if `rules` aren't
+    /// valid
+    pub fn with_seed(rules: Rules, seed: u64) -> Game {
+        Game::with_rng(rules, StdRng::seed_from_u64(seed)).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn with_rng(rules: Rules, mut rng: StdRng) -> Result<Game, RulesError> {
+        rules.validate()?;
+
+        Ok(Game {
+            players: Vec::with_capacity(rules.players),
+            rules,
             dealer_index: 0,
             player_index: 1,
-            deck: Deck::new(),
+            deck: Deck::shuffled(&mut rng),
+            rng,
+            stacked_decks: VecDeque::new(),
+            pinned_dealer: None,
+            first_dealer: None,
             crib: Vec::with_capacity(CRIB_SIZE),
             starter: None,
             played: Vec::with_capacity(PLAYED_SIZE),
+            show_delay: SHOW_DELAY,
+        })
+    }
+
+    /// Set the pause between each hand shown, two seconds by default
+    pub fn set_show_delay(&mut self, delay: Duration) {
+        self.show_delay = delay;
+    }
+
+    /// Deal the next hand from `deck` instead of a shuffled one, e.g. from `Deck::stacked`.
+    /// Stacked decks are used in the order given, then play carries on with shuffled decks
+    pub fn stack_deck(&mut self, deck: Deck) {
+        self.stacked_decks.push_back(deck);
+    }
+
+    /// Give the first deal of each game to `player` rather than cutting for it
+    pub fn set_first_dealer(&mut self, player: usize) {
+        self.pinned_dealer = Some(player);
+    }
+
+    /// The player who had the first deal of the game last started, whether given it or by
+    /// winning the cut
+    pub fn first_dealer(&self) -> Option<usize> {
+        self.first_dealer
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Each player's score, in the order they registered
+    pub fn scores(&self) -> Vec<u16> {
+        self.players.iter().map(|player| player.score).collect()
+    }
+
+    /// The player who reached the target score, or under lowball the lowest scorer outside the
+    /// side that went out, with ties going to the first of them to the left of the player who
+    /// went out. `None` if the game was abandoned or not played yet
+    pub fn winner(&self) -> Option<usize> {
+        let reached = self
+            .players
+            .iter()
+            .position(|player| player.score == self.rules.target_score)?;
+        if !self.rules.lowball {
+            return Some(reached);
         }
+        let partner = self.partner(reached);
+        (1..self.players.len())
+            .map(|offset| (reached + offset) % self.players.len())
+            .filter(|&player_index| Some(player_index) != partner)
+            .min_by_key(|&player_index| self.players[player_index].score)
     }
 
+    pub fn player_id(&self, player: usize) -> &str {
+        &self.players[player].id
+    }
+
+    /// The player sitting opposite `player` when playing in partnerships
+    pub fn partner(&self, player: usize) -> Option<usize> {
+        if !self.rules.partnerships {
+            return None;
+        }
+        Some((player + self.rules.players / 2) % self.rules.players)
+    }
+
+    /// Players are numbered in the order they register, which is the `player` index used by
+    /// the events broadcast during the game
     pub fn register_player(
         &mut self,
         id: String,
         event_sender: SyncSender<GameEvent>,
     ) -> SyncSender<GameAction> {
-        if self.players.len() == 2 {
-            panic!("Can't register more than two players");
+        if self.players.len() == self.rules.players {
+            panic!("Can't register more than {} players", self.rules.players);
         }
 
         let (action_sender, action_receiver) = sync_channel(1);
@@ -55,52 +155,118 @@ impl Game {
         action_sender
     }
 
+    /// Play a game from zero, or each player's handicap, which can be called again to play
+    /// another game with the same players
     pub fn start(&mut self) {
-        if self.players.len() < PLAYERS_SIZE {
+        if self.players.len() < self.rules.players {
             panic!("Can't start")
         }
 
-        self.dealer_index = thread_rng().gen_range(0..self.players.len());
+        self.crib = Vec::with_capacity(CRIB_SIZE);
+        self.starter = None;
+        self.played = Vec::with_capacity(PLAYED_SIZE);
+        for (seat, player) in self.players.iter_mut().enumerate() {
+            player.score = self.rules.handicap.get(seat).copied().unwrap_or(0);
+            player.go = false;
+        }
+        for (seat, player) in self.players.iter().enumerate() {
+            player.send_event(GameEvent::Seat {
+                seat,
+                partner: self.partner(seat),
+                rules: self.rules.clone(),
+            });
+        }
+
+        self.dealer_index = match self.pinned_dealer {
+            Some(player) => player,
+            None => match self.cut_for_deal() {
+                Some(player) => player,
+                None => return,
+            },
+        };
+        self.first_dealer = Some(self.dealer_index);
         self.player_index = (self.dealer_index + 1) % self.players.len();
         println!("{} gets the first deal", self.dealer().id);
+        if self.rules.pone_bonus > 0 {
+            let points = self.rules.pone_bonus;
+            println!("{}: {} for not dealing", self.player().id, points);
+            if self.add_score(self.player_index, points.into(), ScoreReason::PoneBonus) {
+                return;
+            }
+        }
 
         self.game_loop()
     }
 
+    /// Each player cuts the deck and the lowest card deals, cutting again on a tie. Returns
+    /// `None` if a player leaves
+    fn cut_for_deal(&mut self) -> Option<usize> {
+        loop {
+            let mut deck = Deck::shuffled(&mut self.rng);
+            let mut cuts = Vec::with_capacity(self.players.len());
+            for player_index in 0..self.players.len() {
+                let player = &self.players[player_index];
+                let Some(position) = player.await_cut(&deck) else {
+                    println!("{} leaves", player.id);
+                    return None;
+                };
+                let card = deck.cut(position).unwrap();
+                println!("{} cuts {} for deal", player.id, card);
+                self.broadcast(GameEvent::CutForDeal {
+                    player: player_index,
+                    card,
+                });
+                cuts.push(card.run_order());
+            }
+
+            let low = *cuts.iter().min().unwrap();
+            let mut lowest = cuts.iter().positions(|&cut| cut == low);
+            match (lowest.next(), lowest.next()) {
+                (Some(player_index), None) => return Some(player_index),
+                _ => println!("Tie, cut again"),
+            }
+        }
+    }
+
     fn game_loop(&mut self) {
         loop {
             // Deal
             self.deal();
 
+            let discard = self.rules.discard_size();
             for (i, player) in self.players.iter_mut().enumerate() {
                 player.send_event(GameEvent::Deal {
                     cards: player.hand.to_owned(),
                     dealer: i == self.dealer_index,
+                    discard,
                 });
-                let discarded = player.await_discard();
+                let Some(discarded) = player.await_discard(discard) else {
+                    println!("{} leaves", player.id);
+                    return;
+                };
                 self.crib.extend(discarded);
             }
             println!("{} deals", self.dealer().id);
 
-            // Discard
-            for player in self.players.iter_mut() {
-                // let discarded = player.await_discard();
-                // self.crib.extend(discarded);
-            }
-
             // Cut
-            let starter = self.deck.draw().unwrap();
+            let Some(position) = self.player().await_cut(&self.deck) else {
+                println!("{} leaves", self.player().id);
+                return;
+            };
+            let starter = self.deck.cut(position).unwrap();
             self.starter = Some(starter);
             println!("{} cuts {}", self.player().id, starter);
-            if starter.rank() == Rank::Jack {
+            self.broadcast(GameEvent::Starter { card: starter });
+            if self.rules.heels && starter.rank() == Some(Rank::Jack) {
                 println!("{}: 2 for his heels", self.dealer().id);
-                let game_over = self.add_score(self.dealer_index, 2);
+                let game_over = self.add_score(self.dealer_index, 2, ScoreReason::Heels);
                 if game_over {
                     return;
                 }
             }
 
             // Play
+            let mut last_player = None;
             while !self.players.iter().all(|player| player.played_out()) {
                 if self.player().can_play(self.count()) {
                     self.player().send_event(GameEvent::PlayRequest {
@@ -109,12 +275,23 @@ impl Game {
                         count: self.count(),
                     });
                     let count = self.count();
-                    let card = self.player_mut().await_play(count);
+                    let Some(card) = self.player_mut().await_play(count) else {
+                        println!("{} leaves", self.player().id);
+                        return;
+                    };
                     self.played.push(card);
+                    let set_up_by = last_player
+                        .replace(self.player_index)
+                        .filter(|&player| !self.same_side(player, self.player_index));
+                    self.broadcast(GameEvent::Played {
+                        player: self.player_index,
+                        card,
+                        count: self.count(),
+                    });
 
                     let score = score_the_play(&self.played);
                     let mut score_msg = "".to_string();
-                    if score > 0 {
+                    if score > 0 && !self.rules.muggins {
                         score_msg = format!(" for {}", score);
                     }
                     println!(
@@ -125,111 +302,74 @@ impl Game {
                         score_msg
                     );
 
-                    let game_over = self.add_score(self.player_index, score);
-                    if game_over {
+                    let played = self.played.clone();
+                    let stop = self.peg(self.player_index, score, ScoreReason::Play, &played);
+                    if stop {
                         return;
                     }
 
                     if self.count() == MAX_COUNT
                         || self.players.iter().all(|player| player.played_out())
                     {
-                        let game_over;
-                        if self.count() == MAX_COUNT {
+                        let game_over = if self.count() == MAX_COUNT {
                             println!("{}: {} for 2", self.player().id, MAX_COUNT);
-                            game_over = self.add_score(self.player_index, 2);
+                            self.add_score(self.player_index, 2, ScoreReason::ThirtyOne)
                         } else {
                             println!("{}: 1 for last card", self.player().id);
-                            game_over = self.add_score(self.player_index, 1);
-                        }
+                            self.add_score(self.player_index, 1, ScoreReason::LastCard)
+                        };
                         if game_over {
                             return;
                         }
+                        if let (MAX_COUNT, Some(set_up_by)) = (self.count(), set_up_by) {
+                            if self.back_up(set_up_by) {
+                                return;
+                            }
+                        }
 
                         self.played = Vec::with_capacity(PLAYED_SIZE);
+                        last_player = None;
                         for player in self.players.iter_mut() {
                             player.go = false;
                         }
                     }
-                } else if self.next_player().go {
+                } else if self.others_said_go() {
                     println!("{}: 1 for the go", self.player().id);
-                    let game_over = self.add_score(self.player_index, 1);
+                    let game_over = self.add_score(self.player_index, 1, ScoreReason::Go);
                     if game_over {
                         return;
                     }
 
                     self.played = Vec::with_capacity(PLAYED_SIZE);
+                    last_player = None;
                     for player in self.players.iter_mut() {
                         player.go = false;
                     }
                 } else if !self.player().go {
                     self.player_mut().go = true;
                     println!("{}: go", self.player().id);
+                    self.broadcast(GameEvent::Go {
+                        player: self.player_index,
+                    });
                 }
 
                 self.switch_player();
             }
 
-            // Show
-            let shower_i = (self.dealer_index + 1) % 2;
-            let shower = &self.players[shower_i];
-            let score = score_the_show(&shower.hand, &self.starter.unwrap());
-            println!(
-                "{} hand: {} - {} {} {} {} for {}",
-                shower.id,
-                self.starter.unwrap(),
-                shower.hand[0],
-                shower.hand[1],
-                shower.hand[2],
-                shower.hand[3],
-                score,
-            );
-            let game_over = self.add_score(shower_i, score);
-            if game_over {
-                return;
-            }
-
-            thread::sleep(time::Duration::from_secs(2));
-
-            let score = score_the_show(&self.dealer().hand, &self.starter.unwrap());
-            println!(
-                "{} hand: {} - {} {} {} {} for {}",
-                self.dealer().id,
-                self.starter.unwrap(),
-                self.dealer().hand[0],
-                self.dealer().hand[1],
-                self.dealer().hand[2],
-                self.dealer().hand[3],
-                score,
-            );
-            let game_over = self.add_score(self.dealer_index, score);
-            if game_over {
-                return;
+            // Show, starting left of the dealer
+            for i in 1..=self.players.len() {
+                let shower_i = (self.dealer_index + i) % self.players.len();
+                if self.show(shower_i, false) {
+                    return;
+                }
             }
-
-            thread::sleep(time::Duration::from_secs(2));
-
-            let score = score_the_show(&self.crib, &self.starter.unwrap());
-            println!(
-                "{} crib: {} - {} {} {} {} for {}",
-                self.dealer().id,
-                self.starter.unwrap(),
-                self.crib[0],
-                self.crib[1],
-                self.crib[2],
-                self.crib[3],
-                score,
-            );
-            let game_over = self.add_score(self.dealer_index, score);
-            if game_over {
+            if self.show(self.dealer_index, true) {
                 return;
             }
 
-            thread::sleep(time::Duration::from_secs(2));
-
             // Cleanup
-            self.dealer_index = (self.dealer_index + 1) % 2;
-            self.player_index = (self.dealer_index + 1) % 2;
-            self.deck = Deck::new();
+            self.dealer_index = (self.dealer_index + 1) % self.players.len();
+            self.player_index = (self.dealer_index + 1) % self.players.len();
             self.crib = Vec::with_capacity(CRIB_SIZE);
             self.starter = None;
             self.played = Vec::with_capacity(PLAYED_SIZE);
@@ -239,6 +379,142 @@ impl Game {
         }
     }
 
+    /// Score a player's hand, or the crib, with the starter. Returns true if game is over or a
+    /// player left
+    fn show(&mut self, player_index: usize, is_crib: bool) -> bool {
+        let starter = self.starter.unwrap();
+        let (cards, reason) = if is_crib {
+            (self.crib.clone(), ScoreReason::Crib)
+        } else {
+            (self.players[player_index].hand.clone(), ScoreReason::Hand)
+        };
+        let wild = wild_show_score(&cards, &starter, is_crib, &self.rules);
+        let score = wild.score.total();
+        let show = GameEvent::Show {
+            player: player_index,
+            cards: cards.clone(),
+            starter,
+            crib: is_crib,
+            score,
+            substitutions: wild.substitutions,
+        };
+
+        // with muggins the score is only revealed once it has been claimed
+        if !self.rules.muggins {
+            self.announce_show(show.clone());
+        }
+        let stop = self.peg(player_index, score, reason, &cards);
+        if stop {
+            return true;
+        }
+        if self.rules.muggins {
+            self.announce_show(show);
+        }
+        thread::sleep(self.show_delay);
+        false
+    }
+
+    fn announce_show(&self, show: GameEvent) {
+        if let GameEvent::Show {
+            player,
+            cards,
+            starter,
+            crib,
+            score,
+            substitutions,
+        } = &show
+        {
+            println!(
+                "{} {}: {} - {} for {}{}",
+                self.players[*player].id,
+                if *crib { "crib" } else { "hand" },
+                starter,
+                cards.iter().join(" "),
+                score,
+                substitutions
+                    .iter()
+                    .map(|(joker, card)| format!(", {} as {}", joker, card))
+                    .join(""),
+            );
+        }
+        self.broadcast(show);
+    }
+
+    /// Peg `points` made with `cards` for a player. With muggins they must claim the points
+    /// themselves: an over-claim is rejected and scores nothing, and the next player can take
+    /// whatever an under-claim misses. Returns true if game is over or a player left
+    fn peg(
+        &mut self,
+        player_index: usize,
+        points: u8,
+        reason: ScoreReason,
+        cards: &[Card],
+    ) -> bool {
+        if !self.rules.muggins {
+            return self.add_score(player_index, points.into(), reason);
+        }
+
+        let starter = match reason {
+            ScoreReason::Hand | ScoreReason::Crib => self.starter,
+            _ => None,
+        };
+        let player = &self.players[player_index];
+        let request = GameEvent::ClaimRequest {
+            reason,
+            cards: cards.to_owned(),
+            starter,
+        };
+        let Some(claimed) = player.await_claim(request) else {
+            println!("{} leaves", player.id);
+            return true;
+        };
+        if claimed > points {
+            println!("{} claims {} but only has {}", player.id, claimed, points);
+            self.broadcast(GameEvent::ClaimRejected {
+                player: player_index,
+                claimed,
+            });
+            return false;
+        }
+        println!("{} claims {}", player.id, claimed);
+        if self.add_score(player_index, claimed.into(), reason) {
+            return true;
+        }
+
+        // every claim is put to the opponent so that asking doesn't give a miss away
+        let opponent_index = (player_index + 1) % self.players.len();
+        let opponent = &self.players[opponent_index];
+        let request = GameEvent::MugginsRequest {
+            player: player_index,
+            reason,
+            cards: cards.to_owned(),
+            starter,
+            claimed,
+        };
+        let Some(called) = opponent.await_claim(request) else {
+            println!("{} leaves", opponent.id);
+            return true;
+        };
+        if called == 0 {
+            return false;
+        }
+        if called > points - claimed {
+            println!(
+                "{} calls muggins for {} but there's only {}",
+                opponent.id,
+                called,
+                points - claimed
+            );
+            self.broadcast(GameEvent::ClaimRejected {
+                player: opponent_index,
+                claimed: called,
+            });
+            return false;
+        }
+        println!("{}: muggins for {}", opponent.id, called);
+        self.add_score(opponent_index, called.into(), ScoreReason::Muggins)
+    }
+
     fn dealer(&self) -> &Player {
         &self.players[self.dealer_index]
     }
@@ -251,42 +527,95 @@ impl Game {
         &mut self.players[self.player_index]
     }
 
-    fn next_player(&self) -> &Player {
-        &self.players[(self.player_index + 1) % 2]
+    /// Whether two players are the same player or partners
+    fn same_side(&self, player: usize, other: usize) -> bool {
+        player == other || self.partner(player) == Some(other)
     }
 
+    /// Under back up ten, a player whose card was made into 31 by an opponent loses points.
+    /// Returns true if game is over
+    fn back_up(&mut self, player_index: usize) -> bool {
+        if self.rules.back_up == 0 {
+            return false;
+        }
+        let points = self.rules.back_up;
+        println!("{}: back up {}", self.players[player_index].id, points);
+        self.add_score(player_index, -i16::from(points), ScoreReason::BackUp)
+    }
+
+    /// Whether everyone but the player to play has said go, which makes the go theirs
+    fn others_said_go(&self) -> bool {
+        self.players
+            .iter()
+            .enumerate()
+            .all(|(i, player)| i == self.player_index || player.go)
+    }
+
+    /// Deal from the next stacked deck, or a freshly shuffled one, starting left of the dealer.
+    /// When the players' discards won't fill the crib it is topped up from the deck
     fn deal(&mut self) {
-        for player in self.players.iter_mut() {
-            let cards = self.deck.draw_n(6).unwrap();
-            player.set_hand(&cards);
+        self.deck = match self.stacked_decks.pop_front() {
+            Some(deck) => deck,
+            None => Deck::shuffled_with_jokers(self.rules.jokers, &mut self.rng),
+        };
+        for i in 1..=self.players.len() {
+            let cards = self.deck.draw_n(self.rules.deal_size as u8).unwrap();
+            let player_index = (self.dealer_index + i) % self.players.len();
+            self.players[player_index].set_hand(&cards);
         }
+        let short = CRIB_SIZE - self.players.len() * self.rules.discard_size();
+        self.crib.extend(self.deck.draw_n(short as u8).unwrap());
     }
 
-    /// Returns true if game is over
-    fn add_score(&mut self, player_index: usize, score: u8) -> bool {
-        if score == 0 {
+    fn broadcast(&self, event: GameEvent) {
+        for player in self.players.iter() {
+            player.send_event(event.to_owned());
+        }
+    }
+
+    /// Peg for a player, and their partner if they have one, reporting only the points the peg
+    /// actually moved. Returns true if game is over
+    fn add_score(&mut self, player_index: usize, score: i16, reason: ScoreReason) -> bool {
+        let old_score = self.players[player_index].score;
+        let new_score = self.players[player_index].add_score(score, self.rules.target_score);
+        if new_score == old_score {
             return false;
         }
 
-        let new_score = {
-            let player = &mut self.players[player_index];
-            player.add_score(score)
-        };
+        if let Some(partner) = self.partner(player_index) {
+            self.players[partner].score = new_score;
+        }
+        self.broadcast(GameEvent::Scored {
+            player: player_index,
+            points: new_score as i16 - old_score as i16,
+            reason,
+            total: new_score,
+        });
 
         println!(
-            "SCORE {}: {} {}: {}",
-            self.players[0].id, self.players[0].score, self.players[1].id, self.players[1].score
+            "SCORE {}",
+            self.players
+                .iter()
+                .map(|player| format!("{}: {}", player.id, player.score))
+                .join(" ")
         );
 
-        if new_score == MAX_SCORE {
-            println!("{} wins", self.players[player_index].id);
+        if let Some(winner) = self.winner() {
+            if self.rules.lowball {
+                println!("{} goes out", self.players[player_index].id);
+            }
+            println!("{} wins", self.players[winner].id);
+            self.broadcast(GameEvent::GameOver {
+                winner,
+                scores: self.players.iter().map(|player| player.score).collect(),
+            });
             return true;
         }
-        return false;
+        false
     }
 
     fn switch_player(&mut self) {
-        self.player_index = (self.player_index + 1) % PLAYERS_SIZE;
+        self.player_index = (self.player_index + 1) % self.players.len();
     }
 
     fn count(&self) -> u8 {
@@ -298,7 +627,7 @@ struct Player {
     id: String,
     event_sender: SyncSender<GameEvent>,
     action_receiver: Receiver<GameAction>,
-    score: u8,
+    score: u16,
     hand: Vec<Card>,
     played: Vec<Card>,
     go: bool,
@@ -326,22 +655,25 @@ impl Player {
         self.played = Vec::with_capacity(4);
     }
 
-    fn add_score(&mut self, score: u8) -> u8 {
-        self.score = (self.score + score).min(MAX_SCORE);
+    /// Move the peg by `score`, which is negative for a penalty, between zero and the target
+    fn add_score(&mut self, score: i16, target_score: u16) -> u16 {
+        self.score = self.score.saturating_add_signed(score).min(target_score);
         self.score
     }
 
+    /// A player who has left is noticed when the game next waits on them
     fn send_event(&self, event: GameEvent) {
-        self.event_sender.send(event.to_owned()).unwrap();
+        let _ = self.event_sender.send(event);
     }
 
-    fn await_action(&self) -> GameAction {
-        self.action_receiver.recv().unwrap()
+    fn await_action(&self) -> Option<GameAction> {
+        self.action_receiver.recv().ok()
     }
 
-    fn await_discard(&mut self) -> [Card; 2] {
+    /// Wait for `count` cards from the hand to be thrown in the crib
+    fn await_discard(&mut self, count: usize) -> Option<Vec<Card>> {
         loop {
-            let action = self.await_action();
+            let action = self.await_action()?;
             match action {
                 GameAction::Discard { discarded } => {
                     let new_hand = self
@@ -350,9 +682,9 @@ impl Player {
                         .filter(|card| !discarded.contains(card))
                         .map(|card| card.to_owned())
                         .collect_vec();
-                    if new_hand.len() == 4 {
+                    if discarded.len() == count && new_hand.len() + count == self.hand.len() {
                         self.set_hand(&new_hand);
-                        return discarded;
+                        return Some(discarded);
                     }
                     continue;
                 }
@@ -361,6 +693,32 @@ impl Player {
         }
     }
 
+    /// Send a claim or muggins request and wait for the points claimed
+    fn await_claim(&self, request: GameEvent) -> Option<u8> {
+        self.send_event(request);
+        loop {
+            if let GameAction::Claim { points } = self.await_action()? {
+                return Some(points);
+            }
+        }
+    }
+
+    /// Ask for a cut of `deck` until a legal position is chosen
+    fn await_cut(&self, deck: &Deck) -> Option<usize> {
+        let positions = deck.cut_positions();
+        self.send_event(GameEvent::CutRequest {
+            min: *positions.start(),
+            max: *positions.end(),
+        });
+        loop {
+            if let GameAction::Cut { position } = self.await_action()? {
+                if positions.contains(&position) {
+                    return Some(position);
+                }
+            }
+        }
+    }
+
     fn can_play(&self, count: u8) -> bool {
         !self.playable_cards(count).collect_vec().is_empty()
     }
@@ -382,16 +740,16 @@ impl Player {
         self.played.len() == self.hand.len()
     }
 
-    fn await_play(&mut self, count: u8) -> Card {
+    fn await_play(&mut self, count: u8) -> Option<Card> {
         loop {
-            let action = self.await_action();
+            let action = self.await_action()?;
             match action {
                 GameAction::Play { card } => {
                     if !self.playable_cards(count).contains(&card) {
                         continue;
                     }
                     self.played.push(card);
-                    return card;
+                    return Some(card);
                 }
                 _ => continue,
             }
@@ -399,20 +757,631 @@ impl Player {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 pub enum GameEvent {
+    /// Sent to each player as a game starts, saying where they sit, who they play with and the
+    /// rules of the game
+    Seat {
+        seat: usize,
+        partner: Option<usize>,
+        rules: Rules,
+    },
+    /// A new hand, from which `discard` cards must be thrown in the crib
     Deal {
         cards: Vec<Card>,
         dealer: bool,
+        discard: usize,
     },
     PlayRequest {
         hand: Vec<Card>,
         played: Vec<Card>,
         count: u8,
     },
+    /// Choose where to cut the deck, lifting between `min` and `max` cards off the top
+    CutRequest {
+        min: usize,
+        max: usize,
+    },
+    /// A player cut `card` when cutting for the first deal, where the lowest card deals
+    CutForDeal {
+        player: usize,
+        card: Card,
+    },
+    /// The starter has been cut
+    Starter {
+        card: Card,
+    },
+    /// A card was played, bringing the count to `count`
+    Played {
+        player: usize,
+        card: Card,
+        count: u8,
+    },
+    /// A player can't play without going over 31
+    Go {
+        player: usize,
+    },
+    /// A hand, or the crib when `crib` is set, was counted, with each joker paired with the card
+    /// it was counted as
+    Show {
+        player: usize,
+        cards: Vec<Card>,
+        starter: Card,
+        crib: bool,
+        score: u8,
+        substitutions: Vec<(Card, Card)>,
+    },
+    /// Muggins is being played, so claim the points scored with `cards`, which are the play so
+    /// far or a hand or crib shown with `starter`
+    ClaimRequest {
+        reason: ScoreReason,
+        cards: Vec<Card>,
+        starter: Option<Card>,
+    },
+    /// `player` claimed `claimed` points for `cards`, so claim any they missed
+    MugginsRequest {
+        player: usize,
+        reason: ScoreReason,
+        cards: Vec<Card>,
+        starter: Option<Card>,
+        claimed: u8,
+    },
+    /// A player claimed more points than there were, and scores nothing for them
+    ClaimRejected {
+        player: usize,
+        claimed: u8,
+    },
+    /// Points were pegged, bringing the player to `total`. `points` is how far the peg moved,
+    /// which is less than was earned or lost when it stops at the target or at zero
+    Scored {
+        player: usize,
+        points: i16,
+        reason: ScoreReason,
+        total: u16,
+    },
+    GameOver {
+        winner: usize,
+        scores: Vec<u16>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ScoreReason {
+    /// Given to the pone before the first deal under some rules
+    PoneBonus,
+    /// The dealer turned a jack as the starter
+    Heels,
+    /// Fifteens, pairs and runs made during the play
+    Play,
+    /// Last card before neither player could play
+    Go,
+    ThirtyOne,
+    /// Last card of the play
+    LastCard,
+    Hand,
+    Crib,
+    /// Points the opponent missed
+    Muggins,
+    /// Points lost for leaving a count an opponent made 31 from
+    BackUp,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 pub enum GameAction {
-    Discard { discarded: [Card; 2] },
-    Play { card: Card },
+    Discard {
+        discarded: Vec<Card>,
+    },
+    Play {
+        card: Card,
+    },
+    Cut {
+        position: usize,
+    },
+    /// Points claimed in answer to a claim or muggins request
+    Claim {
+        points: u8,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "serde")]
+    use crate::card::Suit;
+    use crate::card::CUT_MARGIN;
+    use crate::the_show::{score_the_crib, score_the_show};
+    use proptest::prelude::*;
+    use rand::Rng;
+    use std::thread::JoinHandle;
+
+    const MAX_SCORE: u16 = 121;
+
+    /// Answers every request with up to `max_mistakes` illegal actions before a random legal
+    /// one, and returns every event it received
+    fn launch_fuzzer(
+        seed: u64,
+        max_mistakes: u8,
+        event_receiver: Receiver<GameEvent>,
+        action_sender: SyncSender<GameAction>,
+    ) -> Vec<GameEvent> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut events = Vec::new();
+        let random_card = |rng: &mut StdRng| Card::from_index(rng.gen_range(0..52)).unwrap();
+        while let Ok(event) = event_receiver.recv() {
+            let mistakes = rng.gen_range(0..=max_mistakes);
+            match &event {
+                GameEvent::Deal { cards, discard, .. } => {
+                    for _ in 0..mistakes {
+                        let action = loop {
+                            let discarded = vec![random_card(&mut rng), random_card(&mut rng)];
+                            if discarded[0] == discarded[1]
+                                || !discarded.iter().all(|card| cards.contains(card))
+                            {
+                                break GameAction::Discard { discarded };
+                            }
+                        };
+                        action_sender.send(action).unwrap();
+                        let card = random_card(&mut rng);
+                        action_sender.send(GameAction::Play { card }).unwrap();
+                    }
+                    let discards = cards.iter().copied().combinations(*discard).collect_vec();
+                    let discarded = discards[rng.gen_range(0..discards.len())].clone();
+                    action_sender
+                        .send(GameAction::Discard { discarded })
+                        .unwrap();
+                }
+                GameEvent::PlayRequest { hand, count, .. } => {
+                    let playable = hand
+                        .iter()
+                        .filter(|card| card.count_value() <= MAX_COUNT - count)
+                        .collect_vec();
+                    for _ in 0..mistakes {
+                        let card = loop {
+                            let card = random_card(&mut rng);
+                            if !playable.contains(&&card) {
+                                break card;
+                            }
+                        };
+                        action_sender.send(GameAction::Play { card }).unwrap();
+                        let discarded = vec![random_card(&mut rng), random_card(&mut rng)];
+                        action_sender
+                            .send(GameAction::Discard { discarded })
+                            .unwrap();
+                    }
+                    let card = *playable[rng.gen_range(0..playable.len())];
+                    action_sender.send(GameAction::Play { card }).unwrap();
+                }
+                GameEvent::CutRequest { min, max } => {
+                    for _ in 0..mistakes {
+                        let position = if rng.gen() {
+                            rng.gen_range(0..*min)
+                        } else {
+                            rng.gen_range(max + 1..100)
+                        };
+                        action_sender.send(GameAction::Cut { position }).unwrap();
+                    }
+                    let position = rng.gen_range(*min..=*max);
+                    action_sender.send(GameAction::Cut { position }).unwrap();
+                }
+                _ => {}
+            }
+            events.push(event);
+        }
+        events
+    }
+
+    fn play_game(seed: u64, player_seeds: [u64; 2], max_mistakes: u8) -> [Vec<GameEvent>; 2] {
+        let mut game = Game::with_seed(Rules::default(), seed);
+        game.set_show_delay(Duration::ZERO);
+        let handles: [JoinHandle<Vec<GameEvent>>; 2] = player_seeds.map(|player_seed| {
+            let (event_sender, event_receiver) = sync_channel(1);
+            let action_sender = game.register_player(player_seed.to_string(), event_sender);
+            thread::spawn(move || {
+                launch_fuzzer(player_seed, max_mistakes, event_receiver, action_sender)
+            })
+        });
+        game.start();
+        drop(game);
+        handles.map(|handle| handle.join().unwrap())
+    }
+
+    /// Replays the events seen by `seat`, checking them against the rules
+    fn check_events(seat: usize, events: &[GameEvent]) {
+        let mut scores = [0u16; 2];
+        let mut dealer = 0;
+        let mut starter = None;
+        let mut sequence: Vec<Card> = Vec::new();
+        let mut last_player = None;
+        let mut hand_played: Vec<Card> = Vec::new();
+        let mut shown: Vec<Card> = Vec::new();
+        let mut last_show = None;
+        let mut cuts_for_deal = Vec::new();
+
+        for (i, event) in events.iter().enumerate() {
+            let count: u8 = sequence.iter().map(|card| card.count_value()).sum();
+            match event {
+                GameEvent::Deal {
+                    cards,
+                    dealer: is_dealer,
+                    discard,
+                } => {
+                    assert_eq!(*discard, 2);
+                    assert_eq!(cards.len(), 6);
+                    dealer = if *is_dealer { seat } else { 1 - seat };
+                    if !cuts_for_deal.is_empty() {
+                        // only the last round counts, and it can't be a tie
+                        let last_round = cuts_for_deal.split_off(cuts_for_deal.len() - 2);
+                        let (low, high) = last_round
+                            .iter()
+                            .sorted_by_key(|(_, card): &&(usize, Card)| card.run_order())
+                            .collect_tuple()
+                            .unwrap();
+                        assert!(low.1.run_order() < high.1.run_order());
+                        assert_eq!(low.0, dealer);
+                        cuts_for_deal.clear();
+                    }
+                    starter = None;
+                    sequence.clear();
+                    last_player = None;
+                    hand_played.clear();
+                    shown.clear();
+                }
+                GameEvent::PlayRequest {
+                    hand,
+                    played,
+                    count: requested,
+                } => {
+                    assert_eq!(played, &sequence);
+                    assert_eq!(*requested, count);
+                    assert!(hand.iter().all(|card| !hand_played.contains(card)));
+                }
+                GameEvent::CutRequest { min, max } => {
+                    assert_eq!(*min, CUT_MARGIN);
+                    assert!(min <= max);
+                }
+                GameEvent::CutForDeal { player, card } => {
+                    assert!(cuts_for_deal.len() % 2 == *player);
+                    cuts_for_deal.push((*player, *card));
+                }
+                GameEvent::Starter { card } => starter = Some(*card),
+                GameEvent::Played {
+                    player,
+                    card,
+                    count,
+                } => {
+                    assert!(!hand_played.contains(card), "{} played twice", card);
+                    hand_played.push(*card);
+                    sequence.push(*card);
+                    last_player = Some(*player);
+                    assert!(*count <= MAX_COUNT);
+                    assert_eq!(*count, count_of(&sequence));
+
+                    // fifteens, pairs and runs are pegged straight after the card
+                    let points = score_the_play(&sequence);
+                    if points > 0 {
+                        let GameEvent::Scored {
+                            reason: ScoreReason::Play,
+                            points: pegged,
+                            ..
+                        } = &events[i + 1]
+                        else {
+                            panic!("{} points for {} not pegged", points, card)
+                        };
+                        let left = (MAX_SCORE - scores[*player]) as i16;
+                        assert_eq!(*pegged, i16::from(points).min(left));
+                    }
+                }
+                GameEvent::Go { .. } => assert!(count < MAX_COUNT),
+                GameEvent::Show {
+                    player,
+                    cards,
+                    starter: shown_starter,
+                    crib,
+                    score,
+                    substitutions,
+                } => {
+                    assert_eq!(cards.len(), 4);
+                    assert!(substitutions.is_empty());
+                    assert_eq!(Some(*shown_starter), starter);
+                    assert_eq!(hand_played.len(), PLAYED_SIZE);
+                    if *crib {
+                        assert_eq!(*player, dealer);
+                        assert_eq!(*score, score_the_crib(cards, shown_starter));
+                        assert!(cards.iter().all(|card| !hand_played.contains(card)));
+                        assert_eq!(
+                            shown
+                                .iter()
+                                .sorted_by_key(|card| card.index())
+                                .collect_vec(),
+                            hand_played
+                                .iter()
+                                .sorted_by_key(|card| card.index())
+                                .collect_vec(),
+                        );
+                    } else {
+                        assert_eq!(*score, score_the_show(cards, shown_starter));
+                        assert!(cards.iter().all(|card| hand_played.contains(card)));
+                        shown.extend(cards);
+                    }
+                    last_show = Some(i16::from(*score));
+                }
+                GameEvent::Scored {
+                    player,
+                    points,
+                    reason,
+                    total,
+                } => {
+                    assert!(*points > 0);
+                    assert_eq!(*total, scores[*player] + *points as u16);
+                    assert!(*total <= MAX_SCORE);
+                    // the peg stops at the target, so the last points can be cut short
+                    let left = (MAX_SCORE - scores[*player]) as i16;
+                    let pegs = |earned: i16| earned.min(left);
+                    scores[*player] = *total;
+                    match reason {
+                        ScoreReason::Heels => {
+                            assert_eq!(*points, pegs(2));
+                            assert_eq!(*player, dealer);
+                            assert_eq!(starter.and_then(|card| card.rank()), Some(Rank::Jack));
+                        }
+                        ScoreReason::Play => {
+                            assert_eq!(last_player, Some(*player));
+                            assert_eq!(*points, pegs(i16::from(score_the_play(&sequence))));
+                        }
+                        ScoreReason::Go | ScoreReason::ThirtyOne | ScoreReason::LastCard => {
+                            assert_eq!(last_player, Some(*player));
+                            match reason {
+                                ScoreReason::Go => {
+                                    assert_eq!(*points, pegs(1));
+                                    assert!(count < MAX_COUNT);
+                                }
+                                ScoreReason::ThirtyOne => {
+                                    assert_eq!(*points, pegs(2));
+                                    assert_eq!(count, MAX_COUNT);
+                                }
+                                _ => {
+                                    assert_eq!(*points, pegs(1));
+                                    assert!(count < MAX_COUNT);
+                                    assert_eq!(hand_played.len(), PLAYED_SIZE);
+                                }
+                            }
+                            sequence.clear();
+                        }
+                        ScoreReason::Hand | ScoreReason::Crib => {
+                            assert_eq!(last_show.map(pegs), Some(*points));
+                        }
+                        ScoreReason::Muggins => panic!("muggins isn't being played"),
+                        ScoreReason::PoneBonus => panic!("the standard game has no pone bonus"),
+                        ScoreReason::BackUp => panic!("the standard game has no back up"),
+                    }
+                }
+                GameEvent::Seat {
+                    seat: player,
+                    partner,
+                    rules,
+                } => {
+                    assert_eq!(i, 0);
+                    assert_eq!((*player, *partner), (seat, None));
+                    assert_eq!(rules, &Rules::default());
+                }
+                GameEvent::ClaimRequest { .. }
+                | GameEvent::MugginsRequest { .. }
+                | GameEvent::ClaimRejected { .. } => panic!("muggins isn't being played"),
+                GameEvent::GameOver {
+                    winner,
+                    scores: final_scores,
+                } => {
+                    assert_eq!(i, events.len() - 1, "events after the game ended");
+                    assert_eq!(final_scores, &scores);
+                    assert_eq!(scores[*winner], MAX_SCORE);
+                    assert!(scores[1 - winner] < MAX_SCORE);
+                }
+            }
+        }
+        assert!(
+            matches!(events.last(), Some(GameEvent::GameOver { .. })),
+            "the game ended without a winner"
+        );
+    }
+
+    fn count_of(cards: &[Card]) -> u8 {
+        cards.iter().map(|card| card.count_value()).sum()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn it_plays_by_the_rules(
+            seed in any::<u64>(),
+            player_seeds in any::<[u64; 2]>(),
+            max_mistakes in 0u8..4,
+        ) {
+            let events = play_game(seed, player_seeds, max_mistakes);
+            for (seat, events) in events.iter().enumerate() {
+                check_events(seat, events);
+            }
+        }
+    }
+
+    #[test]
+    fn it_picks_the_lowball_winner_away_from_the_side_that_went_out() {
+        let winner = |rules: Rules, scores: &[u16]| {
+            let mut game = Game::with_seed(rules, 0);
+            for &score in scores {
+                let (event_sender, _) = sync_channel(1);
+                game.register_player(score.to_string(), event_sender);
+                game.players.last_mut().unwrap().score = score;
+            }
+            game.winner()
+        };
+
+        let three = Rules {
+            lowball: true,
+            ..Rules::three_player()
+        };
+        assert_eq!(winner(three.clone(), &[121, 50, 50]), Some(1));
+        assert_eq!(winner(three.clone(), &[50, 121, 50]), Some(2));
+        assert_eq!(winner(three.clone(), &[40, 121, 50]), Some(0));
+        assert_eq!(winner(three, &[40, 100, 50]), None);
+
+        let four = Rules {
+            lowball: true,
+            ..Rules::four_player()
+        };
+        assert_eq!(winner(four.clone(), &[60, 121, 60, 121]), Some(2));
+        assert_eq!(winner(four, &[121, 80, 121, 80]), Some(1));
+    }
+
+    #[test]
+    fn it_reports_the_points_the_peg_moved() {
+        let mut game = Game::with_seed(
+            Rules {
+                back_up: 10,
+                ..Rules::default()
+            },
+            0,
+        );
+        let receivers = ["Dealer", "Pone"].map(|id| {
+            let (event_sender, event_receiver) = sync_channel(4);
+            game.register_player(id.to_string(), event_sender);
+            event_receiver
+        });
+        game.players[1].score = 5;
+
+        assert!(!game.back_up(1));
+        assert_eq!(
+            receivers[0].try_recv().ok(),
+            Some(GameEvent::Scored {
+                player: 1,
+                points: -5,
+                reason: ScoreReason::BackUp,
+                total: 0
+            })
+        );
+        assert!(!game.back_up(1));
+        assert!(receivers[0].try_recv().is_err());
+
+        game.players[0].score = 119;
+        assert!(game.add_score(0, 4, ScoreReason::Play));
+        assert_eq!(
+            receivers[0].try_recv().ok(),
+            Some(GameEvent::Scored {
+                player: 0,
+                points: 2,
+                reason: ScoreReason::Play,
+                total: 121
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_rules() {
+        let rules = Rules {
+            players: 5,
+            ..Rules::default()
+        };
+        assert!(matches!(
+            Game::try_new(rules),
+            Err(RulesError::Unsupported(_))
+        ));
+        assert!(Game::try_new(Rules::four_player()).is_ok());
+    }
+
+    #[test]
+    fn it_cuts_for_the_first_deal_of_every_game_unless_given() {
+        let mut game = Game::with_seed(Rules::default(), 3);
+        game.set_show_delay(Duration::ZERO);
+        let handles: [JoinHandle<Vec<GameEvent>>; 2] = [1, 2].map(|player_seed| {
+            let (event_sender, event_receiver) = sync_channel(1);
+            let action_sender = game.register_player(player_seed.to_string(), event_sender);
+            thread::spawn(move || launch_fuzzer(player_seed, 0, event_receiver, action_sender))
+        });
+        game.start();
+        game.start();
+        game.set_first_dealer(1);
+        game.start();
+        assert_eq!(game.first_dealer(), Some(1));
+        drop(game);
+
+        let [events, _] = handles.map(|handle| handle.join().unwrap());
+        let cuts = events
+            .split(|event| matches!(event, GameEvent::GameOver { .. }))
+            .map(|game| {
+                game.iter()
+                    .filter(|event| matches!(event, GameEvent::CutForDeal { .. }))
+                    .count()
+            })
+            .collect_vec();
+        assert_eq!(cuts.len(), 4);
+        assert!(cuts[0] >= 2 && cuts[1] >= 2);
+        assert_eq!(cuts[2], 0);
+    }
+
+    #[test]
+    fn it_replays_a_seeded_game() {
+        assert_eq!(play_game(7, [1, 2], 2), play_game(7, [1, 2], 2));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_events() {
+        let event = GameEvent::PlayRequest {
+            hand: vec![Card::new(Suit::Hearts, Rank::Five)],
+            played: vec![Card::new(Suit::Spades, Rank::Ten)],
+            count: 10,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"play_request","hand":["5H"],"played":["10S"],"count":10}"#
+        );
+        assert_eq!(serde_json::from_str::<GameEvent>(&json).unwrap(), event);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_actions() {
+        let action = GameAction::Discard {
+            discarded: vec![
+                Card::new(Suit::Clubs, Rank::Jack),
+                Card::new(Suit::Diamonds, Rank::Ace),
+            ],
+        };
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"type":"discard","discarded":["JC","AD"]}"#);
+        assert_eq!(serde_json::from_str::<GameAction>(&json).unwrap(), action);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_score_events() {
+        let event = GameEvent::Scored {
+            player: 1,
+            points: 2,
+            reason: ScoreReason::ThirtyOne,
+            total: 47,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"scored","player":1,"points":2,"reason":"thirty_one","total":47}"#
+        );
+        assert_eq!(serde_json::from_str::<GameEvent>(&json).unwrap(), event);
+    }
 }
@@ -1,8 +1,10 @@
 use crate::{
     card::Card,
-    game::{GameAction, GameEvent},
+    game::{GameAction, GameEvent, ScoreReason},
+};
+use inquire::{
+    list_option::ListOption, validator::Validation, CustomType, InquireError, MultiSelect, Select,
 };
-use inquire::{list_option::ListOption, validator::Validation, InquireError, MultiSelect, Select};
 use itertools::Itertools;
 use std::sync::mpsc::{Receiver, SyncSender};
 
@@ -10,8 +12,12 @@ pub fn launch_human(event_receiver: Receiver<GameEvent>, action_sender: SyncSend
     loop {
         let event = event_receiver.recv();
         match event {
-            Ok(GameEvent::Deal { cards, dealer }) => {
-                let discarded = discard_cards(cards, dealer);
+            Ok(GameEvent::Deal {
+                cards,
+                dealer,
+                discard,
+            }) => {
+                let discarded = discard_cards(cards, discard, dealer);
                 let Ok(_) = action_sender.send(GameAction::Discard { discarded }) else {
                     break;
                 };
@@ -26,6 +32,60 @@ pub fn launch_human(event_receiver: Receiver<GameEvent>, action_sender: SyncSend
                     break;
                 };
             }
+            Ok(GameEvent::ClaimRequest {
+                reason,
+                cards,
+                starter,
+            }) => {
+                let prompt = format!("Points for {}:", describe(reason, &cards, starter));
+                let points = select_points(&prompt);
+                let Ok(_) = action_sender.send(GameAction::Claim { points }) else {
+                    break;
+                };
+            }
+            Ok(GameEvent::MugginsRequest {
+                reason,
+                cards,
+                starter,
+                claimed,
+                ..
+            }) => {
+                let prompt = format!(
+                    "They claim {} for {}. Muggins points:",
+                    claimed,
+                    describe(reason, &cards, starter)
+                );
+                let points = select_points(&prompt);
+                let Ok(_) = action_sender.send(GameAction::Claim { points }) else {
+                    break;
+                };
+            }
+            Ok(GameEvent::Seat {
+                seat,
+                partner,
+                rules,
+            }) => {
+                if let Some(partner) = partner {
+                    println!(
+                        "You are player {}, partnered with player {}",
+                        seat + 1,
+                        partner + 1
+                    );
+                }
+                if rules.lowball {
+                    println!("Lowball: the first to {} loses", rules.target_score);
+                }
+            }
+            Ok(GameEvent::ClaimRejected { claimed, .. }) => {
+                println!("A claim of {} was rejected", claimed);
+            }
+            Ok(GameEvent::CutRequest { min, max }) => {
+                let position = select_cut(min, max);
+                let Ok(_) = action_sender.send(GameAction::Cut { position }) else {
+                    break;
+                };
+            }
+            Ok(_) => {}
             Err(_) => {
                 break;
             }
@@ -33,22 +93,19 @@ pub fn launch_human(event_receiver: Receiver<GameEvent>, action_sender: SyncSend
     }
 }
 
-fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
-    let validator = |a: &[ListOption<&Card>]| {
-        if a.len() != 2 {
-            return Ok(Validation::Invalid("Select 2 cards".into()));
+fn discard_cards(cards: Vec<Card>, discard: usize, dealer: bool) -> Vec<Card> {
+    let validator = move |a: &[ListOption<&Card>]| {
+        if a.len() != discard {
+            return Ok(Validation::Invalid(
+                format!("Select {} cards", discard).into(),
+            ));
         }
-        return Ok(Validation::Valid);
+        Ok(Validation::Valid)
     };
 
-    let whose_crib;
-    if dealer {
-        whose_crib = "your crib";
-    } else {
-        whose_crib = "their crib";
-    }
+    let whose_crib = if dealer { "your crib" } else { "their crib" };
     let answer = MultiSelect::new(
-        &format!("Select 2 cards to discard to {}:", whose_crib),
+        &format!("Select {} cards to discard to {}:", discard, whose_crib),
         cards.clone(),
     )
     .without_help_message()
@@ -57,9 +114,9 @@ fn discard_cards(cards: Vec<Card>, dealer: bool) -> [Card; 2] {
     .prompt();
 
     match answer {
-        Ok(discarded) => [discarded[0], discarded[1]],
+        Ok(discarded) => discarded,
         Err(err) => match err {
-            InquireError::OperationCanceled => discard_cards(cards, dealer),
+            InquireError::OperationCanceled => discard_cards(cards, discard, dealer),
             _ => panic!(),
         },
     }
@@ -91,3 +148,49 @@ fn select_play(hand: Vec<Card>, _played: Vec<Card>, count: u8) -> Card {
         }
     }
 }
+
+fn select_cut(min: usize, max: usize) -> usize {
+    loop {
+        let answer = CustomType::<usize>::new(&format!("Cut the deck ({} to {} cards):", min, max))
+            .with_error_message("Enter a number of cards")
+            .prompt();
+
+        match answer {
+            Ok(position) if (min..=max).contains(&position) => return position,
+            Ok(_) => {
+                println!("Leave at least four cards on each side");
+                continue;
+            }
+            Err(err) => match err {
+                InquireError::OperationCanceled => continue,
+                _ => panic!(),
+            },
+        }
+    }
+}
+
+fn describe(reason: ScoreReason, cards: &[Card], starter: Option<Card>) -> String {
+    let cards = cards.iter().join(" ");
+    match (reason, starter) {
+        (ScoreReason::Crib, Some(starter)) => format!("the crib {} - {}", starter, cards),
+        (_, Some(starter)) => format!("{} - {}", starter, cards),
+        _ => format!("the play {}", cards),
+    }
+}
+
+fn select_points(prompt: &str) -> u8 {
+    loop {
+        let answer = CustomType::<u8>::new(prompt)
+            .with_default(0)
+            .with_error_message("Enter a number of points")
+            .prompt();
+
+        match answer {
+            Ok(points) => return points,
+            Err(err) => match err {
+                InquireError::OperationCanceled => continue,
+                _ => panic!(),
+            },
+        }
+    }
+}
@@ -0,0 +1,16 @@
+pub mod ai;
+pub mod analysis;
+pub mod canonical;
+pub mod card;
+pub mod card_set;
+pub mod discard_table;
+pub mod game;
+pub mod human;
+pub mod match_play;
+#[cfg(test)]
+mod reference;
+pub mod rules;
+pub mod scripted;
+pub mod show_table;
+pub mod the_play;
+pub mod the_show;
@@ -1,29 +1,207 @@
-use ai::launch_ai;
-use game::Game;
-use human::launch_human;
-use std::{sync::mpsc::sync_channel, thread};
+use clap::{Parser, Subcommand, ValueEnum};
+use cribbage::{
+    ai::launch_ai,
+    card::Card,
+    game::Game,
+    match_play::{Match, MatchLength},
+    rules::Rules,
+};
+use std::{path::PathBuf, process::ExitCode, sync::mpsc::sync_channel, thread};
 
-mod ai;
-mod card;
-mod game;
-mod human;
-mod the_play;
-mod the_show;
+mod cli;
 
-fn main() {
-    let mut game = Game::new();
+#[derive(Parser)]
+#[command(about = "Play and analyze cribbage")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// A TOML file of house rules, e.g. `heels = false`
+    #[arg(long, global = true)]
+    rules: Option<PathBuf>,
+    /// Play a variant with its own rules instead of the standard game
+    #[arg(long, global = true, conflicts_with = "rules")]
+    variant: Option<Variant>,
+    /// Same as `--variant five-card`
+    #[arg(long, global = true, conflicts_with_all = ["rules", "variant"])]
+    five_card: bool,
+    /// Same as `--variant seven-card`
+    #[arg(long, global = true, conflicts_with_all = ["rules", "variant", "five_card"])]
+    seven_card: bool,
+}
 
-    {
-        let (event_sender, event_receiver) = sync_channel(1);
-        let action_sender = game.register_player("CPU".into(), event_sender);
-        thread::spawn(move || launch_ai(event_receiver, action_sender));
+#[derive(Clone, Copy, ValueEnum)]
+enum Variant {
+    /// To 61
+    ShortGame,
+    /// To 181
+    LongGame,
+    /// Five cards dealt, to 61
+    FiveCard,
+    /// Seven cards dealt and two thrown to a four card crib, to 181
+    SevenCard,
+    /// Three players dealt five cards each
+    ThreePlayer,
+    /// Two partnerships of two
+    FourPlayer,
+    /// The first to 121 loses
+    Lowball,
+    /// Two wild jokers in the deck
+    Jokers,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Count a hand, e.g. `cribbage score 5H 5D 5S JC --starter 5C`
+    Score {
+        /// The cards in the hand
+        #[arg(required = true)]
+        cards: Vec<Card>,
+        /// The starter card
+        #[arg(long)]
+        starter: Card,
+        /// Score as the crib, where only a five card flush counts
+        #[arg(long)]
+        crib: bool,
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rank every discard from a six card deal, e.g. `cribbage discard 2H 3C 5D 5S JH KD --dealer`
+    Discard {
+        /// The six cards dealt
+        #[arg(required = true)]
+        cards: Vec<Card>,
+        /// Whether the crib is yours
+        #[arg(long)]
+        dealer: bool,
+    },
+    /// Play a match of several games, e.g. `cribbage match --best-of 3`
+    Match {
+        /// Play until someone wins more than half of this many games
+        #[arg(
+            long,
+            conflicts_with = "first_to",
+            required_unless_present = "first_to"
+        )]
+        best_of: Option<u32>,
+        /// Play until someone reaches this many game points, two for a win and more for a skunk
+        #[arg(long)]
+        first_to: Option<u32>,
+    },
+    /// Build or query the table of best discards used by the AI when `CRIBBAGE_DISCARD_TABLE`
+    /// points to it
+    DiscardTable {
+        #[command(subcommand)]
+        command: DiscardTableCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DiscardTableCommand {
+    /// Evaluate every six card deal and write the table to `path`
+    Generate { path: PathBuf },
+    /// Look up the best discard from a six card deal by estimated net points
+    Lookup {
+        path: PathBuf,
+        /// The six cards dealt
+        #[arg(required = true)]
+        cards: Vec<Card>,
+        /// Whether the crib is yours
+        #[arg(long)]
+        dealer: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let variant = if cli.five_card {
+        Some(Variant::FiveCard)
+    } else if cli.seven_card {
+        Some(Variant::SevenCard)
+    } else {
+        cli.variant
+    };
+    let rules = match variant {
+        Some(Variant::ShortGame) => Ok(Rules::short_game()),
+        Some(Variant::LongGame) => Ok(Rules::long_game()),
+        Some(Variant::FiveCard) => Ok(Rules::five_card()),
+        Some(Variant::SevenCard) => Ok(Rules::seven_card()),
+        Some(Variant::ThreePlayer) => Ok(Rules::three_player()),
+        Some(Variant::FourPlayer) => Ok(Rules::four_player()),
+        Some(Variant::Lowball) => Ok(Rules::lowball()),
+        Some(Variant::Jokers) => Ok(Rules::jokers()),
+        None => cli::load_rules(cli.rules.as_deref()),
+    };
+    let rules = match rules {
+        Ok(rules) => rules,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match cli.command {
+        None => play(rules),
+        Some(Command::Score {
+            cards,
+            starter,
+            crib,
+            json,
+        }) => cli::score(&cards, starter, crib, json, &rules),
+        Some(Command::Discard { cards, dealer }) => cli::discard(&cards, dealer),
+        Some(Command::Match { best_of, first_to }) => {
+            let length = match (best_of, first_to) {
+                (Some(games), _) => MatchLength::BestOf(games),
+                (None, Some(points)) => MatchLength::FirstTo(points),
+                (None, None) => unreachable!(),
+            };
+            play_match(rules, length)
+        }
+        Some(Command::DiscardTable {
+            command: DiscardTableCommand::Generate { path },
+        }) => cli::generate_discard_table(&path),
+        Some(Command::DiscardTable {
+            command:
+                DiscardTableCommand::Lookup {
+                    path,
+                    cards,
+                    dealer,
+                },
+        }) => cli::lookup_discard(&path, &cards, dealer),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
     }
+}
+
+fn play(rules: Rules) -> Result<(), String> {
+    new_game(rules)?.start();
+    Ok(())
+}
+
+fn play_match(rules: Rules, length: MatchLength) -> Result<(), String> {
+    let summary = Match::new(new_game(rules)?, length).play();
+    println!("{}", summary);
+    Ok(())
+}
+
+fn new_game(rules: Rules) -> Result<Game, String> {
+    let players = rules.players;
+    let mut game = Game::try_new(rules).map_err(|err| err.to_string())?;
 
+    for id in ["CPU", "T-800", "HAL 9000", "Deep Thought"]
+        .into_iter()
+        .take(players)
     {
         let (event_sender, event_receiver) = sync_channel(1);
-        let action_sender = game.register_player("T-800".into(), event_sender);
+        let action_sender = game.register_player(id.into(), event_sender);
         thread::spawn(move || launch_ai(event_receiver, action_sender));
     }
 
-    game.start();
+    Ok(game)
 }
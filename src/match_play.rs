@@ -0,0 +1,291 @@
+use crate::{game::Game, rules::Rules};
+use std::fmt;
+
+/// When a match is over
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchLength {
+    /// The first player to win more than half of this many games
+    BestOf(u32),
+    /// The first player to this many game points
+    FirstTo(u32),
+}
+
+/// Game points awarded to the winner of each game, by how far behind the loser finished. The
+/// skunk lines come from the game's `Rules`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatchScoring {
+    pub win: u32,
+    pub skunk: u32,
+    pub double_skunk: u32,
+}
+
+impl Default for MatchScoring {
+    /// Standard tournament scoring
+    fn default() -> Self {
+        MatchScoring {
+            win: 2,
+            skunk: 3,
+            double_skunk: 4,
+        }
+    }
+}
+
+impl MatchScoring {
+    /// How a game was won given the loser's score, and the game points it's worth
+    pub fn score(&self, rules: &Rules, loser_score: u16) -> (Outcome, u32) {
+        if loser_score < rules.double_skunk_line {
+            (Outcome::DoubleSkunk, self.double_skunk)
+        } else if loser_score < rules.skunk_line {
+            (Outcome::Skunk, self.skunk)
+        } else {
+            (Outcome::Win, self.win)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Win,
+    Skunk,
+    DoubleSkunk,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Win => write!(f, "win"),
+            Outcome::Skunk => write!(f, "skunk"),
+            Outcome::DoubleSkunk => write!(f, "double skunk"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameSummary {
+    pub first_dealer: usize,
+    pub winner: usize,
+    pub scores: Vec<u16>,
+    pub outcome: Outcome,
+    /// Game points awarded to the winner
+    pub points: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchSummary {
+    pub players: Vec<String>,
+    pub games: Vec<GameSummary>,
+    /// Game points for each player
+    pub points: Vec<u32>,
+    /// Games won by each player
+    pub wins: Vec<u32>,
+    /// `None` if a player left before the match was decided
+    pub winner: Option<usize>,
+}
+
+impl fmt::Display for MatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, game) in self.games.iter().enumerate() {
+            let scores = game.scores.iter().map(|score| score.to_string());
+            writeln!(
+                f,
+                "Game {}: {} {} {} for {}",
+                i + 1,
+                self.players[game.winner],
+                game.outcome,
+                scores.collect::<Vec<_>>().join("-"),
+                game.points,
+            )?;
+        }
+        for (i, player) in self.players.iter().enumerate() {
+            writeln!(
+                f,
+                "{}: {} games, {} points",
+                player, self.wins[i], self.points[i]
+            )?;
+        }
+        match self.winner {
+            Some(winner) => write!(f, "{} wins the match", self.players[winner]),
+            None => write!(f, "Match abandoned"),
+        }
+    }
+}
+
+/// A series of games between the players registered with a `Game`
+pub struct Match {
+    game: Game,
+    length: MatchLength,
+    scoring: MatchScoring,
+}
+
+impl Match {
+    pub fn new(game: Game, length: MatchLength) -> Match {
+        Match {
+            game,
+            length,
+            scoring: MatchScoring::default(),
+        }
+    }
+
+    pub fn set_scoring(&mut self, scoring: MatchScoring) {
+        self.scoring = scoring;
+    }
+
+    /// Play games until the match is decided, cutting for the first deal of the first game and
+    /// alternating it after that
+    pub fn play(&mut self) -> MatchSummary {
+        let players = self.game.scores().len();
+        let mut summary = MatchSummary {
+            players: (0..players)
+                .map(|player| self.game.player_id(player).to_owned())
+                .collect(),
+            games: Vec::new(),
+            points: vec![0; players],
+            wins: vec![0; players],
+            winner: None,
+        };
+
+        while summary.winner.is_none() {
+            self.game.start();
+            let Some(winner) = self.game.winner() else {
+                break;
+            };
+            let scores = self.game.scores();
+            let partner = self.game.partner(winner);
+            let loser_score = scores
+                .iter()
+                .enumerate()
+                .filter(|(player, _)| *player != winner && Some(*player) != partner)
+                .map(|(_, score)| *score)
+                .max()
+                .unwrap_or_default();
+            let (outcome, points) = self.scoring.score(self.game.rules(), loser_score);
+            let first_dealer = self.game.first_dealer().unwrap();
+
+            summary.points[winner] += points;
+            summary.wins[winner] += 1;
+            summary.games.push(GameSummary {
+                first_dealer,
+                winner,
+                scores,
+                outcome,
+                points,
+            });
+            println!(
+                "{} wins game {} ({}) for {}",
+                summary.players[winner],
+                summary.games.len(),
+                outcome,
+                points
+            );
+
+            let decided = match self.length {
+                MatchLength::BestOf(games) => summary.wins[winner] > games / 2,
+                MatchLength::FirstTo(points) => summary.points[winner] >= points,
+            };
+            if decided {
+                summary.winner = Some(winner);
+            }
+            self.game.set_first_dealer((first_dealer + 1) % players);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{GameAction, GameEvent};
+    use itertools::Itertools;
+    use std::{
+        sync::mpsc::{sync_channel, Receiver, SyncSender},
+        thread,
+        time::Duration,
+    };
+
+    /// Discards the first cards dealt, plays the first card it can and cuts as shallow as it may
+    fn launch_first_choice(
+        event_receiver: Receiver<GameEvent>,
+        action_sender: SyncSender<GameAction>,
+    ) {
+        while let Ok(event) = event_receiver.recv() {
+            let action = match event {
+                GameEvent::Deal { cards, discard, .. } => GameAction::Discard {
+                    discarded: cards[..discard].to_vec(),
+                },
+                GameEvent::PlayRequest { hand, count, .. } => GameAction::Play {
+                    card: *hand
+                        .iter()
+                        .find(|card| card.count_value() + count <= 31)
+                        .unwrap(),
+                },
+                GameEvent::CutRequest { min, .. } => GameAction::Cut { position: min },
+                _ => continue,
+            };
+            if action_sender.send(action).is_err() {
+                break;
+            }
+        }
+    }
+
+    fn new_match(length: MatchLength) -> Match {
+        let mut game = Game::with_seed(Rules::default(), 3);
+        game.set_show_delay(Duration::ZERO);
+        for id in ["A", "B"] {
+            let (event_sender, event_receiver) = sync_channel(1);
+            let action_sender = game.register_player(id.into(), event_sender);
+            thread::spawn(move || launch_first_choice(event_receiver, action_sender));
+        }
+        Match::new(game, length)
+    }
+
+    #[test]
+    fn it_scores_skunks() {
+        let scoring = MatchScoring::default();
+        let rules = Rules::default();
+        assert_eq!(scoring.score(&rules, 120), (Outcome::Win, 2));
+        assert_eq!(scoring.score(&rules, 91), (Outcome::Win, 2));
+        assert_eq!(scoring.score(&rules, 90), (Outcome::Skunk, 3));
+        assert_eq!(scoring.score(&rules, 61), (Outcome::Skunk, 3));
+        assert_eq!(scoring.score(&rules, 60), (Outcome::DoubleSkunk, 4));
+        assert_eq!(scoring.score(&rules, 0), (Outcome::DoubleSkunk, 4));
+    }
+
+    #[test]
+    fn it_plays_best_of_five() {
+        let summary = new_match(MatchLength::BestOf(5)).play();
+
+        let winner = summary.winner.unwrap();
+        assert_eq!(summary.wins[winner], 3);
+        assert!(summary.wins[1 - winner] < 3);
+        assert_eq!(summary.games.len() as u32, summary.wins.iter().sum::<u32>());
+        assert_eq!(summary.games.last().unwrap().winner, winner);
+        for (game, next) in summary.games.iter().tuple_windows() {
+            assert_eq!(next.first_dealer, 1 - game.first_dealer);
+        }
+        for game in summary.games.iter() {
+            assert_eq!(game.scores[game.winner], 121);
+            assert_eq!(
+                (game.outcome, game.points),
+                MatchScoring::default().score(&Rules::default(), game.scores[1 - game.winner])
+            );
+        }
+    }
+
+    #[test]
+    fn it_plays_to_a_number_of_points() {
+        let mut game_match = new_match(MatchLength::FirstTo(7));
+        game_match.set_scoring(MatchScoring {
+            win: 1,
+            skunk: 1,
+            double_skunk: 1,
+        });
+        let summary = game_match.play();
+
+        let winner = summary.winner.unwrap();
+        assert_eq!(summary.points[winner], 7);
+        assert_eq!(summary.points, summary.wins);
+        assert!(summary
+            .to_string()
+            .ends_with(&format!("{} wins the match", summary.players[winner])));
+    }
+}
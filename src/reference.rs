@@ -0,0 +1,165 @@
+//! A deliberately naive show scorer written straight from the rules, for checking the real
+//! scorers against. It looks at every subset of the five cards one at a time and makes no
+//! attempt to be fast.
+
+use crate::card::{Card, Rank};
+
+/// Score `hand` with `starter` by checking every subset of the cards against the rules
+pub fn score(hand: &[Card], starter: Card, is_crib: bool) -> u8 {
+    let mut cards = hand.to_vec();
+    cards.push(starter);
+    let subsets: Vec<Vec<Card>> = (1..1u32 << cards.len())
+        .map(|mask| {
+            (0..cards.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| cards[i])
+                .collect()
+        })
+        .collect();
+
+    let mut score = 0;
+
+    // Fifteens: two points for each combination of cards that adds up to 15
+    for subset in &subsets {
+        let total: u8 = subset.iter().map(|card| card.count_value()).sum();
+        if total == 15 {
+            score += 2;
+        }
+    }
+
+    // Pairs: two points for each pair of cards of the same rank
+    for subset in &subsets {
+        if subset.len() == 2 && subset[0].rank() == subset[1].rank() {
+            score += 2;
+        }
+    }
+
+    // Runs: one point per card for each combination of three or more cards in sequence that
+    // isn't part of a longer sequence
+    for subset in &subsets {
+        if subset.len() < 3 || !is_run(subset) {
+            continue;
+        }
+        let part_of_longer_run = subsets.iter().any(|other| {
+            other.len() > subset.len()
+                && is_run(other)
+                && subset.iter().all(|card| other.contains(card))
+        });
+        if !part_of_longer_run {
+            score += subset.len() as u8;
+        }
+    }
+
+    // Flush: four points if every card in the hand is the same suit, five if the starter is
+    // too. A crib only counts the five card flush.
+    let suit = hand[0].suit();
+    if hand.iter().all(|card| card.suit() == suit) {
+        if starter.suit() == suit {
+            score += hand.len() as u8 + 1;
+        } else if !is_crib {
+            score += hand.len() as u8;
+        }
+    }
+
+    // Nobs: one point for the jack of the starter's suit in the hand
+    if hand
+        .iter()
+        .any(|card| card.rank() == Some(Rank::Jack) && card.suit() == starter.suit())
+    {
+        score += 1;
+    }
+
+    score
+}
+
+fn is_run(cards: &[Card]) -> bool {
+    let Some(mut orders) = cards
+        .iter()
+        .map(|card| card.run_order())
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    orders.sort();
+    orders.windows(2).all(|pair| pair[1] == pair[0] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        card::parse_hand,
+        card_set::CardSet,
+        show_table::ShowTable,
+        the_show::{show_score, show_score_set},
+    };
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    fn reference_score(cards: &str, is_crib: bool) -> u8 {
+        let (hand, starter) = parse_hand(cards).unwrap();
+        score(&hand, starter.unwrap(), is_crib)
+    }
+
+    #[test]
+    fn it_scores_known_hands() {
+        assert_eq!(reference_score("5H 5D 5S JC | 5C", false), 29);
+        assert_eq!(reference_score("2H 4C 6D 8S | 10H", false), 0);
+        assert_eq!(reference_score("KS KH QD JS | 10C", false), 10);
+        assert_eq!(reference_score("QS QH QD JS | 10C", false), 15);
+        assert_eq!(reference_score("QS 10S 8S 6S | 4C", false), 4);
+        assert_eq!(reference_score("QS 10S 8S 6S | 4C", true), 0);
+        assert_eq!(reference_score("QS 10S 8S 6S | 4S", true), 5);
+        assert_eq!(reference_score("3S 3H 4D 4C | 5H", false), 20);
+    }
+
+    #[test]
+    fn it_agrees_with_the_show_on_random_hands() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut deck = CardSet::FULL.iter().collect_vec();
+        for _ in 0..20_000 {
+            deck.shuffle(&mut rng);
+            let (hand, starter) = (&deck[..4], deck[4]);
+            for is_crib in [false, true] {
+                assert_eq!(
+                    show_score(hand, &starter, is_crib).total(),
+                    score(hand, starter, is_crib)
+                );
+            }
+        }
+    }
+
+    /// The number of hands and starters giving each score from 0 to 29
+    const DISTRIBUTION: [u32; 30] = [
+        1_009_008, 99_792, 2_813_796, 505_008, 2_855_676, 697_508, 1_800_268, 751_324, 1_137_236,
+        361_224, 388_740, 51_680, 317_340, 19_656, 90_100, 9_168, 58_248, 11_196, 2_708, 0, 8_068,
+        2_496, 444, 356, 3_680, 0, 0, 0, 76, 4,
+    ];
+
+    #[test]
+    #[ignore = "scores all 13 million hands several ways, run with --release --ignored"]
+    fn it_agrees_with_every_scorer_on_every_hand() {
+        let table = ShowTable::shared();
+        let mut distribution = [0u32; 30];
+        for hand in CardSet::FULL.iter().combinations(4) {
+            let set: CardSet = hand.iter().collect();
+            for starter in !set {
+                for is_crib in [false, true] {
+                    let expected = score(&hand, starter, is_crib);
+                    assert_eq!(show_score(&hand, &starter, is_crib).total(), expected);
+                    assert_eq!(
+                        show_score_set(set, starter, is_crib).unwrap().total(),
+                        expected
+                    );
+                    assert_eq!(table.score(set, starter, is_crib), expected);
+                }
+                distribution[score(&hand, starter, false) as usize] += 1;
+            }
+        }
+
+        assert_eq!(distribution, DISTRIBUTION);
+        for impossible in [19, 25, 26, 27] {
+            assert_eq!(distribution[impossible], 0);
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use crate::card::{CRIB_SIZE, MAX_JOKERS};
+use std::{error, fmt};
+
+/// How a flush counts in the crib
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CribFlush {
+    /// Only when the starter matches the four crib cards
+    FiveCard,
+    /// Four crib cards of one suit count, as they do in a hand
+    FourCard,
+}
+
+/// House rules for a game. Any field left out of a TOML file keeps its default, except the skunk
+/// lines, which follow the target score
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Rules {
+    /// Players at the table
+    pub players: usize,
+    /// Whether players sitting opposite each other are partners who pool their score
+    pub partnerships: bool,
+    /// Points needed to win
+    pub target_score: u16,
+    /// Cards dealt to each player
+    pub deal_size: usize,
+    /// A loser below this many points is skunked
+    pub skunk_line: u16,
+    /// A loser below this many points is double skunked
+    pub double_skunk_line: u16,
+    /// Points the pone pegs before the first deal, to make up for not dealing
+    pub pone_bonus: u8,
+    /// Points each player starts the game with, by seat, for a handicap
+    pub handicap: Vec<u16>,
+    /// Points a player goes back when an opponent makes 31 from their card, 10 in back up ten
+    pub back_up: u8,
+    /// Whether the dealer pegs 2 when a jack is cut as the starter
+    pub heels: bool,
+    /// Whether the jack of the starter's suit scores 1 in the show
+    pub nobs: bool,
+    pub crib_flush: CribFlush,
+    /// Whether players must claim their own points
+    pub muggins: bool,
+    /// Whether the first to `target_score` loses, leaving the lowest score the winner
+    pub lowball: bool,
+    /// Jokers shuffled into the deck, which are wild in the show and count nothing in the play
+    pub jokers: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            players: 2,
+            partnerships: false,
+            target_score: 121,
+            deal_size: 6,
+            skunk_line: 91,
+            double_skunk_line: 61,
+            pone_bonus: 0,
+            handicap: Vec::new(),
+            back_up: 0,
+            heels: true,
+            nobs: true,
+            crib_flush: CribFlush::FiveCard,
+            muggins: false,
+            lowball: false,
+            jokers: 0,
+        }
+    }
+}
+
+impl Rules {
+    /// Once around a 61 hole board, with a skunk below 31
+    pub fn short_game() -> Rules {
+        Rules {
+            target_score: 61,
+            skunk_line: 31,
+            double_skunk_line: 0,
+            ..Rules::default()
+        }
+    }
+
+    /// Three times around a 61 hole board
+    pub fn long_game() -> Rules {
+        Rules {
+            target_score: 181,
+            skunk_line: 151,
+            double_skunk_line: 121,
+            ..Rules::default()
+        }
+    }
+
+    /// The older five-card game: five cards dealt, three kept, and 61 points to win with the
+    /// pone given 3 at the start
+    pub fn five_card() -> Rules {
+        Rules {
+            deal_size: 5,
+            pone_bonus: 3,
+            ..Rules::short_game()
+        }
+    }
+
+    /// Seven cards dealt and five kept, with 181 points to win. Each player throws two, so the
+    /// crib holds four cards as in the standard game
+    pub fn seven_card() -> Rules {
+        Rules {
+            deal_size: 7,
+            ..Rules::long_game()
+        }
+    }
+
+    /// Three players dealt five cards each, with a card dealt to the crib
+    pub fn three_player() -> Rules {
+        Rules {
+            players: 3,
+            deal_size: 5,
+            ..Rules::default()
+        }
+    }
+
+    /// Two partnerships of two, dealt five cards each
+    pub fn four_player() -> Rules {
+        Rules {
+            players: 4,
+            partnerships: true,
+            deal_size: 5,
+            ..Rules::default()
+        }
+    }
+
+    /// Losing cribbage, where the first to 121 loses
+    pub fn lowball() -> Rules {
+        Rules {
+            lowball: true,
+            ..Rules::default()
+        }
+    }
+
+    /// Two jokers in the deck, each counted in the show as whatever card scores best
+    pub fn jokers() -> Rules {
+        Rules {
+            jokers: 2,
+            ..Rules::default()
+        }
+    }
+
+    /// Cards each player throws in the crib
+    pub fn discard_size(&self) -> usize {
+        CRIB_SIZE / self.players
+    }
+
+    /// Check that the game can be played with these rules
+    pub fn validate(&self) -> Result<(), RulesError> {
+        if !(2..=4).contains(&self.players) {
+            return Err(RulesError::Unsupported(format!(
+                "a game of {} players",
+                self.players
+            )));
+        }
+        if self.partnerships && self.players != 4 {
+            return Err(RulesError::Invalid(
+                "partnerships need four players".to_string(),
+            ));
+        }
+        if self.players > 2 && self.deal_size != 5 {
+            return Err(RulesError::Unsupported(format!(
+                "a deal of {} cards with {} players",
+                self.deal_size, self.players
+            )));
+        }
+        if !(5..=7).contains(&self.deal_size) {
+            return Err(RulesError::Unsupported(format!(
+                "a deal of {} cards",
+                self.deal_size
+            )));
+        }
+        if self.jokers > MAX_JOKERS {
+            return Err(RulesError::Unsupported(format!(
+                "a deck with {} jokers",
+                self.jokers
+            )));
+        }
+        if self.muggins && self.lowball {
+            return Err(RulesError::Unsupported("muggins in lowball".to_string()));
+        }
+        if self.target_score == 0 {
+            return Err(RulesError::Invalid(
+                "target_score must be above 0".to_string(),
+            ));
+        }
+        if self.double_skunk_line > self.skunk_line || self.skunk_line > self.target_score {
+            return Err(RulesError::Invalid(
+                "skunk lines must be in order and no higher than target_score".to_string(),
+            ));
+        }
+        if u16::from(self.pone_bonus) >= self.target_score {
+            return Err(RulesError::Invalid(
+                "pone_bonus must be below target_score".to_string(),
+            ));
+        }
+        if self.handicap.len() > self.players
+            || self
+                .handicap
+                .iter()
+                .any(|&start| start >= self.target_score)
+        {
+            return Err(RulesError::Invalid(
+                "handicap needs at most one start per player, each below target_score".to_string(),
+            ));
+        }
+        let start = |seat: usize| self.handicap.get(seat).copied().unwrap_or(0);
+        if self.partnerships && (start(0) != start(2) || start(1) != start(3)) {
+            return Err(RulesError::Invalid(
+                "partners must have the same handicap".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse and validate rules from TOML, e.g. `target_score = 61`. Skunk lines that aren't
+    /// given sit 30 and 60 points below the target, as they do in the standard game
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Rules, RulesError> {
+        let parse_error = |err: toml::de::Error| RulesError::Parse(err.to_string());
+        let mut rules: Rules = toml::from_str(s).map_err(parse_error)?;
+        let given: toml::Table = toml::from_str(s).map_err(parse_error)?;
+        if !given.contains_key("skunk_line") {
+            rules.skunk_line = rules.target_score.saturating_sub(30);
+        }
+        if !given.contains_key("double_skunk_line") {
+            rules.double_skunk_line = rules.target_score.saturating_sub(60);
+        }
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Read rules from a TOML file
+    #[cfg(feature = "toml")]
+    pub fn load(path: &std::path::Path) -> Result<Rules, RulesError> {
+        let s = std::fs::read_to_string(path).map_err(|err| RulesError::Parse(err.to_string()))?;
+        Rules::from_toml(&s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RulesError {
+    Parse(String),
+    Invalid(String),
+    /// A rule this version can't play yet
+    Unsupported(String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::Parse(err) => write!(f, "couldn't read rules: {}", err),
+            RulesError::Invalid(err) => write!(f, "invalid rules: {}", err),
+            RulesError::Unsupported(rule) => write!(f, "{} isn't supported", rule),
+        }
+    }
+}
+
+impl error::Error for RulesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_validates_rules() {
+        assert_eq!(Rules::default().validate(), Ok(()));
+        assert_eq!(Rules::five_card().validate(), Ok(()));
+        assert_eq!(Rules::seven_card().validate(), Ok(()));
+        assert_eq!(Rules::three_player().validate(), Ok(()));
+        assert_eq!(Rules::three_player().discard_size(), 1);
+        assert_eq!(Rules::four_player().validate(), Ok(()));
+        let rules = Rules {
+            partnerships: true,
+            ..Rules::default()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Invalid(_))));
+        let rules = Rules {
+            skunk_line: 130,
+            ..Rules::default()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Invalid(_))));
+        let rules = Rules {
+            handicap: vec![0, 121],
+            ..Rules::default()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Invalid(_))));
+        let rules = Rules {
+            handicap: vec![10, 0, 0, 0],
+            ..Rules::four_player()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Invalid(_))));
+        let rules = Rules {
+            muggins: true,
+            ..Rules::lowball()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Unsupported(_))));
+        assert_eq!(Rules::jokers().validate(), Ok(()));
+        let rules = Rules {
+            jokers: 3,
+            ..Rules::default()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Unsupported(_))));
+        let rules = Rules {
+            deal_size: 8,
+            ..Rules::default()
+        };
+        assert!(matches!(rules.validate(), Err(RulesError::Unsupported(_))));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn it_loads_house_rules_from_toml() {
+        let rules = Rules::from_toml(
+            r#"
+            heels = false
+            crib_flush = "four_card"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            rules,
+            Rules {
+                heels: false,
+                crib_flush: CribFlush::FourCard,
+                ..Rules::default()
+            }
+        );
+        assert!(matches!(
+            Rules::from_toml("target = 61"),
+            Err(RulesError::Parse(_))
+        ));
+        let rules = Rules::from_toml("target_score = 61").unwrap();
+        assert_eq!((rules.skunk_line, rules.double_skunk_line), (31, 1));
+        let rules = Rules::from_toml("target_score = 61\ndouble_skunk_line = 0").unwrap();
+        assert_eq!((rules.skunk_line, rules.double_skunk_line), (31, 0));
+        assert!(matches!(
+            Rules::from_toml("target_score = 61\nskunk_line = 91"),
+            Err(RulesError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    card::Card,
+    game::{GameAction, GameEvent},
+};
+use itertools::Itertools;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::mpsc::{Receiver, SyncSender},
+};
+
+/// One step of a scripted player's game
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// Answer the next deal by throwing these cards in the crib
+    Discard(Vec<Card>),
+    /// Answer the next play request with this card
+    Play(Card),
+    /// Answer the next cut request by lifting this many cards
+    Cut(usize),
+    /// Answer the next claim or muggins request with this many points
+    Claim(u8),
+    /// This event must arrive before the player acts again
+    Expect(GameEvent),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Discard(discarded) => write!(f, "discard {}", discarded.iter().join(" ")),
+            Step::Play(card) => write!(f, "play {}", card),
+            Step::Cut(position) => write!(f, "cut {}", position),
+            Step::Claim(points) => write!(f, "claim {}", points),
+            Step::Expect(event) => write!(f, "expect {:?}", event),
+        }
+    }
+}
+
+/// Plays `script` step by step, panicking when the game asks for something the script doesn't
+/// have next or an expected event never arrives. Events between expectations are not checked.
+/// The player leaves, which ends the game, once the script runs out and it is dealt another
+/// hand. Returns every event received
+pub fn launch_scripted(
+    script: Vec<Step>,
+    event_receiver: Receiver<GameEvent>,
+    action_sender: SyncSender<GameAction>,
+) -> Vec<GameEvent> {
+    let mut script = VecDeque::from(script);
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.recv() {
+        events.push(event.to_owned());
+        match (&event, script.front()) {
+            (_, Some(Step::Expect(expected))) if *expected == event => {
+                script.pop_front();
+            }
+            (GameEvent::Deal { .. }, None) => break,
+            (GameEvent::Deal { .. }, Some(Step::Discard(discarded))) => {
+                let discarded = discarded.to_owned();
+                script.pop_front();
+                let Ok(_) = action_sender.send(GameAction::Discard { discarded }) else {
+                    break;
+                };
+            }
+            (GameEvent::PlayRequest { .. }, Some(Step::Play(card))) => {
+                let card = *card;
+                script.pop_front();
+                let Ok(_) = action_sender.send(GameAction::Play { card }) else {
+                    break;
+                };
+            }
+            (GameEvent::CutRequest { .. }, Some(Step::Cut(position))) => {
+                let position = *position;
+                script.pop_front();
+                let Ok(_) = action_sender.send(GameAction::Cut { position }) else {
+                    break;
+                };
+            }
+            (
+                GameEvent::ClaimRequest { .. } | GameEvent::MugginsRequest { .. },
+                Some(Step::Claim(points)),
+            ) => {
+                let points = *points;
+                script.pop_front();
+                let Ok(_) = action_sender.send(GameAction::Claim { points }) else {
+                    break;
+                };
+            }
+            (
+                GameEvent::Deal { .. }
+                | GameEvent::PlayRequest { .. }
+                | GameEvent::CutRequest { .. }
+                | GameEvent::ClaimRequest { .. }
+                | GameEvent::MugginsRequest { .. },
+                step,
+            ) => {
+                panic!(
+                    "got {:?} but the script has {}",
+                    event,
+                    step.map_or("nothing left".to_string(), |step| step.to_string())
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(Step::Expect(expected)) = script.iter().find(|step| matches!(step, Step::Expect(_)))
+    {
+        panic!("never got {:?}", expected);
+    }
+    events
+}
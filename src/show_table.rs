@@ -0,0 +1,223 @@
+use crate::{
+    canonical::canonicalize,
+    card::{Card, Suit},
+    card_set::CardSet,
+    the_show::show_score_set,
+};
+use itertools::Itertools;
+use std::{
+    io::{self, Read, Write},
+    sync::OnceLock,
+};
+use strum::IntoEnumIterator;
+
+const MAGIC: &[u8; 8] = b"CRIBSHOW";
+const VERSION: u8 = 1;
+const BITS_PER_SCORE: usize = 5;
+const HAND_SIZE: usize = 4;
+const STARTERS: usize = 52 - HAND_SIZE;
+const HANDS: usize = binomial(52, HAND_SIZE);
+const ENTRIES: usize = HANDS * STARTERS;
+
+/// The show score of every four card hand with every starter
+///
+/// Scores are packed five bits apiece and indexed by the hand's position in the combinatorial
+/// number system and the starter's position among the 48 cards left, so a lookup is a few
+/// additions and a shift. Only hand scores are stored, a crib only differs by losing a four card
+/// flush.
+pub struct ShowTable {
+    data: Vec<u8>,
+}
+
+impl ShowTable {
+    /// Compute every score, scoring only one hand from each set of hands that are the same up to
+    /// relabeling suits and copying the rest
+    pub fn generate() -> ShowTable {
+        let mut table = ShowTable {
+            data: vec![0; packed_len()],
+        };
+
+        let hands = CardSet::FULL.iter().combinations(HAND_SIZE).map(|hand| {
+            let hand: CardSet = hand.iter().collect();
+            let (canonical, suits) = canonicalize(hand);
+            (hand, canonical, suits)
+        });
+        let (canonical_hands, other_hands): (Vec<_>, Vec<_>) =
+            hands.partition(|(hand, canonical, _)| hand == canonical);
+
+        for (hand, _, _) in canonical_hands {
+            for starter in hand.complement() {
+                let score = show_score_set(hand, starter, false)
+                    .expect("a CardSet holds no jokers")
+                    .total();
+                table.set(index(hand, starter), score);
+            }
+        }
+        for (hand, canonical, suits) in other_hands {
+            for starter in hand.complement() {
+                let starter_index = index(canonical, suits.apply_card(starter));
+                table.set(index(hand, starter), table.get(starter_index));
+            }
+        }
+        table
+    }
+
+    /// The show score of `hand` with `starter`
+    ///
+    /// Panics if `hand` doesn't hold exactly four cards.
+    pub fn score(&self, hand: CardSet, starter: Card, is_crib: bool) -> u8 {
+        assert_eq!(hand.len() as usize, HAND_SIZE, "hand must hold four cards");
+        let score = self.get(index(hand, starter));
+        if is_crib && is_four_card_flush(hand, starter) {
+            return score - 4;
+        }
+        score
+    }
+
+    /// A table shared by the whole program, generated on first use
+    pub fn shared() -> &'static ShowTable {
+        static TABLE: OnceLock<ShowTable> = OnceLock::new();
+        TABLE.get_or_init(ShowTable::generate)
+    }
+
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, BITS_PER_SCORE as u8])?;
+        writer.write_all(&(ENTRIES as u32).to_le_bytes())?;
+        writer.write_all(&self.data[..packed_len()])
+    }
+
+    pub fn read_from(mut reader: impl Read) -> io::Result<ShowTable> {
+        let mut header = [0; 14];
+        reader.read_exact(&mut header)?;
+        let entries = u32::from_le_bytes(header[10..14].try_into().unwrap());
+        if &header[..8] != MAGIC
+            || header[8] != VERSION
+            || header[9] != BITS_PER_SCORE as u8
+            || entries as usize != ENTRIES
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a show score table",
+            ));
+        }
+
+        let mut data = vec![0; packed_len()];
+        reader.read_exact(&mut data[..packed_len()])?;
+        Ok(ShowTable { data })
+    }
+
+    fn get(&self, index: usize) -> u8 {
+        let bit = index * BITS_PER_SCORE;
+        let word = u16::from_le_bytes([self.data[bit / 8], self.data[bit / 8 + 1]]);
+        ((word >> (bit % 8)) & SCORE_MASK) as u8
+    }
+
+    fn set(&mut self, index: usize, score: u8) {
+        let bit = index * BITS_PER_SCORE;
+        let mut word = u16::from_le_bytes([self.data[bit / 8], self.data[bit / 8 + 1]]);
+        word &= !(SCORE_MASK << (bit % 8));
+        word |= (score as u16) << (bit % 8);
+        [self.data[bit / 8], self.data[bit / 8 + 1]] = word.to_le_bytes();
+    }
+}
+
+const SCORE_MASK: u16 = (1 << BITS_PER_SCORE) - 1;
+
+/// The show score of `hand` with `starter` from a table generated on first use
+///
+/// Panics if `hand` doesn't hold exactly four cards.
+pub fn score_lookup(hand: CardSet, starter: Card, is_crib: bool) -> u8 {
+    ShowTable::shared().score(hand, starter, is_crib)
+}
+
+/// Bytes needed for every packed score, plus one so a two byte read never runs off the end
+fn packed_len() -> usize {
+    (ENTRIES * BITS_PER_SCORE).div_ceil(8) + 1
+}
+
+fn index(hand: CardSet, starter: Card) -> usize {
+    let hand_index: usize = hand
+        .iter()
+        .enumerate()
+        .map(|(i, card)| BINOMIAL[card.index() as usize][i + 1])
+        .sum();
+    let below_starter = CardSet::from_bits((1 << starter.index()) - 1).unwrap();
+    let starter_index = starter.index() as usize - (hand & below_starter).len() as usize;
+    hand_index * STARTERS + starter_index
+}
+
+fn is_four_card_flush(hand: CardSet, starter: Card) -> bool {
+    Suit::iter().any(|suit| Some(suit) != starter.suit() && hand.suit_mask(suit).count_ones() == 4)
+}
+
+const BINOMIAL: [[usize; HAND_SIZE + 1]; 52] = {
+    let mut table = [[0; HAND_SIZE + 1]; 52];
+    let mut n = 0;
+    while n < 52 {
+        let mut k = 0;
+        while k <= HAND_SIZE {
+            table[n][k] = binomial(n, k);
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+};
+
+const fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let mut result = 1;
+    let mut i = 0;
+    while i < k {
+        result = result * (n - i) / (i + 1);
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    #[test]
+    fn it_indexes_every_entry_once() {
+        let hands = [
+            CardSet::FULL.iter().take(4).collect::<CardSet>(),
+            CardSet::FULL.iter().skip(48).collect(),
+        ];
+        assert_eq!(index(hands[0], Card::from_index(4).unwrap()), 0);
+        assert_eq!(index(hands[1], Card::from_index(47).unwrap()), ENTRIES - 1);
+    }
+
+    #[test]
+    #[ignore = "generates the whole table, run with --release --ignored"]
+    fn it_looks_up_the_same_scores_as_the_show() {
+        let table = ShowTable::generate();
+        let mut rng = StdRng::seed_from_u64(32);
+        let mut deck = CardSet::FULL.iter().collect_vec();
+        for _ in 0..20_000 {
+            deck.shuffle(&mut rng);
+            let hand: CardSet = deck[..4].iter().collect();
+            let starter = deck[4];
+            for is_crib in [false, true] {
+                assert_eq!(
+                    table.score(hand, starter, is_crib),
+                    show_score_set(hand, starter, is_crib).unwrap().total()
+                );
+            }
+        }
+
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 14 + packed_len());
+        let read = ShowTable::read_from(bytes.as_slice()).unwrap();
+        assert!(read.data == table.data);
+
+        bytes[0] = b'X';
+        assert!(ShowTable::read_from(bytes.as_slice()).is_err());
+    }
+}
@@ -1,12 +1,21 @@
 use crate::card::Card;
 use itertools::Itertools;
 
+/// A joker isn't wild in the play: it scores nothing when played, and no pair or run can be made
+/// through it
 pub fn score_the_play(cards: &[Card]) -> u8 {
+    if cards.last().is_some_and(Card::is_joker) {
+        return 0;
+    }
     let mut score = 0;
 
     score += count_fifteen(cards);
-    score += count_pairs(cards);
-    score += count_runs(cards);
+    let since_joker = match cards.iter().rposition(Card::is_joker) {
+        Some(position) => &cards[position + 1..],
+        None => cards,
+    };
+    score += count_pairs(since_joker);
+    score += count_runs(since_joker);
 
     score
 }
@@ -43,14 +52,13 @@ fn count_runs(cards: &[Card]) -> u8 {
             break;
         }
 
-        let mut cards = cards[cards.len() - n..].to_owned();
-        cards.sort_by(|a, b| a.run_cmp(b));
-        let start = cards[0].run_order();
-        if cards
+        let mut orders = cards[cards.len() - n..]
             .iter()
-            .map(|card| card.run_order())
-            .eq(start..start + n as u8)
-        {
+            .filter_map(Card::run_order)
+            .collect_vec();
+        orders.sort();
+        let start = orders[0];
+        if orders.into_iter().eq(start..start + n as u8) {
             score = n as u8;
         }
     }
@@ -60,7 +68,7 @@ fn count_runs(cards: &[Card]) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::card::{Card, Rank, Suit};
+    use crate::card::{Card, Rank, Suit, JOKERS};
 
     #[test]
     fn it_counts_fifteens() {
@@ -178,4 +186,31 @@ mod tests {
         let score = score_the_play(&cards);
         assert_eq!(score, 7)
     }
+
+    #[test]
+    fn it_scores_nothing_for_a_joker() {
+        let joker = JOKERS[0];
+        let fifteen = vec![
+            Card::new(Suit::Spades, Rank::Five),
+            Card::new(Suit::Hearts, Rank::Ten),
+            joker,
+        ];
+        assert_eq!(score_the_play(&fifteen), 0);
+
+        let pair = vec![
+            Card::new(Suit::Spades, Rank::Ace),
+            joker,
+            Card::new(Suit::Hearts, Rank::Ace),
+        ];
+        assert_eq!(score_the_play(&pair), 0);
+
+        let run = vec![
+            Card::new(Suit::Spades, Rank::Four),
+            joker,
+            Card::new(Suit::Spades, Rank::Five),
+            Card::new(Suit::Spades, Rank::Six),
+        ];
+        // fifteen for 2, but no run through the joker
+        assert_eq!(score_the_play(&run), 2);
+    }
 }
@@ -1,39 +1,239 @@
-use crate::card::{Card, Rank};
+use crate::{
+    card::{Card, Rank, Suit},
+    card_set::{jack_of, CardSet},
+    rules::{CribFlush, Rules},
+};
 use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+/// Points scored in the show, broken down by combination
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShowScore {
+    pub fifteens: u8,
+    pub pairs: u8,
+    pub runs: u8,
+    pub flush: u8,
+    pub nobs: u8,
+}
+
+impl ShowScore {
+    pub fn total(&self) -> u8 {
+        self.fifteens + self.pairs + self.runs + self.flush + self.nobs
+    }
+}
+
+/// The best show score with jokers wild, and the card each joker was counted as
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WildShowScore {
+    pub score: ShowScore,
+    /// Each joker in the hand and then the starter, paired with the card it became
+    pub substitutions: Vec<(Card, Card)>,
+}
 
 pub fn score_the_show(hand: &[Card], starter: &Card) -> u8 {
+    show_score(hand, starter, false).total()
+}
+
+/// Like `score_the_show`, but a flush only counts when the starter matches too
+pub fn score_the_crib(crib: &[Card], starter: &Card) -> u8 {
+    show_score(crib, starter, true).total()
+}
+
+/// Any jokers are counted as whichever cards score the most
+pub fn show_score(hand: &[Card], starter: &Card, is_crib: bool) -> ShowScore {
+    best_substitution(hand, starter, |hand, starter| {
+        natural_show_score(hand, starter, is_crib)
+    })
+    .score
+}
+
+/// Like `show_score_with_rules`, but also says what each joker was counted as
+pub fn wild_show_score(
+    hand: &[Card],
+    starter: &Card,
+    is_crib: bool,
+    rules: &Rules,
+) -> WildShowScore {
+    let five_card_flush = is_crib && rules.crib_flush == CribFlush::FiveCard;
+    best_substitution(hand, starter, |hand, starter| {
+        let mut score = natural_show_score(hand, starter, five_card_flush);
+        if !rules.nobs {
+            score.nobs = 0;
+        }
+        score
+    })
+}
+
+/// Tries every card not already shown in place of each joker and keeps the highest `score`,
+/// the first found on a tie
+fn best_substitution(
+    hand: &[Card],
+    starter: &Card,
+    score: impl Fn(&[Card], &Card) -> ShowScore,
+) -> WildShowScore {
+    // most hands have no joker, so check before allocating anything
+    if !starter.is_joker() && !hand.iter().any(Card::is_joker) {
+        return WildShowScore {
+            score: score(hand, starter),
+            substitutions: Vec::new(),
+        };
+    }
+    let jokers = hand
+        .iter()
+        .chain([starter])
+        .filter(|card| card.is_joker())
+        .collect_vec();
+
+    let candidates = Card::all()
+        .filter(|card| !hand.contains(card) && card != starter)
+        .collect_vec();
+    let mut best: Option<WildShowScore> = None;
+    for cards in jokers
+        .iter()
+        .map(|_| candidates.iter().copied())
+        .multi_cartesian_product()
+    {
+        if !cards.iter().all_unique() {
+            continue;
+        }
+        let substitute = |card: &Card| match jokers.iter().position(|joker| *joker == card) {
+            Some(i) => cards[i],
+            None => *card,
+        };
+        let hand = hand.iter().map(substitute).collect_vec();
+        let wild_score = score(&hand, &substitute(starter));
+        if best
+            .as_ref()
+            .is_none_or(|best| wild_score.total() > best.score.total())
+        {
+            best = Some(WildShowScore {
+                score: wild_score,
+                substitutions: jokers.iter().copied().copied().zip(cards).collect(),
+            });
+        }
+    }
+    best.unwrap()
+}
+
+/// Scores cards once any jokers have been substituted
+fn natural_show_score(hand: &[Card], starter: &Card, is_crib: bool) -> ShowScore {
     let mut cards = hand.to_owned();
     cards.push(starter.to_owned());
     cards.sort_by(|a, b| a.run_cmp(b));
 
-    let mut score = 0;
+    ShowScore {
+        fifteens: count_fifteens(&cards),
+        pairs: count_pairs(&cards),
+        runs: count_runs(&cards),
+        flush: count_flush(hand, starter, is_crib),
+        nobs: count_nobs(hand, starter),
+    }
+}
+
+/// Like `show_score`, but with the nobs and crib flush house rules in `rules`
+pub fn show_score_with_rules(
+    hand: &[Card],
+    starter: &Card,
+    is_crib: bool,
+    rules: &Rules,
+) -> ShowScore {
+    wild_show_score(hand, starter, is_crib, rules).score
+}
+
+/// Same as `show_score`, but works from rank counts and suit masks without allocating. Returns
+/// `None` if the starter is a joker, which can't be in a `CardSet`
+pub fn show_score_set(hand: CardSet, starter: Card, is_crib: bool) -> Option<ShowScore> {
+    let suit = starter.suit()?;
+    let counts = hand.with(starter).rank_counts();
+    Some(ShowScore {
+        fifteens: count_fifteens_from_ranks(&counts),
+        pairs: counts.iter().map(|n| n * n.saturating_sub(1)).sum(),
+        runs: count_runs_from_ranks(&counts),
+        flush: count_flush_from_set(hand, suit, is_crib),
+        nobs: if (hand & jack_of(suit)).is_empty() {
+            0
+        } else {
+            1
+        },
+    })
+}
 
-    score += count_fifteens(&cards);
-    score += count_pairs(&cards);
-    score += count_runs(&cards);
-    score += count_flush(&hand, &starter);
-    score += count_nobs(&hand, &starter);
+/// Counts subsets summing to 15 by building up the number of ways to make each total rank by rank
+fn count_fifteens_from_ranks(counts: &[u8; 13]) -> u8 {
+    let mut ways = [0u16; 16];
+    ways[0] = 1;
+    for (run_order, &n) in counts.iter().enumerate() {
+        if n == 0 {
+            continue;
+        }
+        let value = (run_order + 1).min(10);
+        let previous = ways;
+        let mut choose = 1;
+        for k in 1..=n as usize {
+            // choose = n choose k
+            choose = choose * (n as u16 + 1 - k as u16) / k as u16;
+            let added = k * value;
+            if added > 15 {
+                break;
+            }
+            for total in 0..=15 - added {
+                ways[total + added] += previous[total] * choose;
+            }
+        }
+    }
+    (ways[15] * 2) as u8
+}
 
+/// Each maximal sequence of three or more ranks scores its length once per way to pick one card
+/// of each rank
+fn count_runs_from_ranks(counts: &[u8; 13]) -> u8 {
+    let mut score = 0;
+    let mut length = 0;
+    let mut ways = 1;
+    for n in counts.iter().chain([&0]) {
+        if *n > 0 {
+            length += 1;
+            ways *= n;
+            continue;
+        }
+        if length >= 3 {
+            score += length * ways;
+        }
+        length = 0;
+        ways = 1;
+    }
     score
 }
 
+fn count_flush_from_set(hand: CardSet, starter_suit: Suit, is_crib: bool) -> u8 {
+    let len = hand.len();
+    if len == 0 || hand.suit_mask(starter_suit).count_ones() == len {
+        return if len == 0 { 0 } else { len as u8 + 1 };
+    }
+    if is_crib {
+        return 0;
+    }
+    let flushed = Suit::iter().any(|suit| hand.suit_mask(suit).count_ones() == len);
+    if flushed {
+        len as u8
+    } else {
+        0
+    }
+}
+
 fn count_fifteens(cards: &[Card]) -> u8 {
-    let card_combinations = (2..=5)
-        .map(|size| cards.iter().combinations(size))
-        .into_iter()
-        .flatten();
+    let card_combinations = (2..=cards.len()).flat_map(|size| cards.iter().combinations(size));
 
     let counts =
         card_combinations.map(|cards| cards.iter().map(|card| card.count_value()).sum::<u8>());
 
-    let score = counts.map(|count| if count == 15 { 2 } else { 0 }).sum();
-    score
+    counts.map(|count| if count == 15 { 2 } else { 0 }).sum()
 }
 
 fn count_pairs(cards: &[Card]) -> u8 {
     let card_combinations = cards.iter().combinations(2);
 
-    let score = card_combinations
+    card_combinations
         .map(|cards| {
             if cards[0].rank() == cards[1].rank() {
                 2
@@ -41,8 +241,7 @@ fn count_pairs(cards: &[Card]) -> u8 {
                 0
             }
         })
-        .sum();
-    score
+        .sum()
 }
 
 /// Assumes `cards` are already sorted
@@ -60,20 +259,22 @@ fn count_runs(cards: &[Card]) -> u8 {
     0
 }
 
-fn count_flush(hand: &[Card], starter: &Card) -> u8 {
+fn count_flush(hand: &[Card], starter: &Card, is_crib: bool) -> u8 {
     let suit = hand[0].suit();
     if hand[1..].iter().all(|card| card.suit() == suit) {
         if starter.suit() == suit {
-            return 5;
+            return hand.len() as u8 + 1;
+        }
+        if !is_crib {
+            return hand.len() as u8;
         }
-        return 4;
     }
     0
 }
 
 fn count_nobs(hand: &[Card], starter: &Card) -> u8 {
     for card in hand.iter() {
-        if card.rank() == Rank::Jack && card.suit() == starter.suit() {
+        if card.rank() == Some(Rank::Jack) && card.suit() == starter.suit() {
             return 1;
         }
     }
@@ -82,12 +283,12 @@ fn count_nobs(hand: &[Card], starter: &Card) -> u8 {
 
 fn count_run(cards: &[&Card]) -> u8 {
     let n = cards.len() as u8;
-    let start = cards[0].run_order();
-    if cards
+    let orders = cards
         .iter()
-        .map(|card| card.run_order())
-        .eq(start..start + n)
-    {
+        .filter_map(|card| card.run_order())
+        .collect_vec();
+    let start = orders[0];
+    if orders.into_iter().eq(start..start + n) {
         return n;
     }
     0
@@ -96,7 +297,46 @@ fn count_run(cards: &[&Card]) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::card::{Card, Rank, Suit};
+    use crate::card::{Card, Rank, Suit, JOKERS};
+
+    #[test]
+    fn it_applies_house_rules() {
+        let crib = vec![
+            Card::new(Suit::Hearts, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Four),
+            Card::new(Suit::Hearts, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Jack),
+        ];
+        let starter = Card::new(Suit::Hearts, Rank::Ace);
+        let standard = show_score_with_rules(&crib, &starter, true, &Rules::default());
+        assert_eq!((standard.flush, standard.nobs), (5, 1));
+
+        let starter = Card::new(Suit::Clubs, Rank::Ace);
+        let standard = show_score_with_rules(&crib, &starter, true, &Rules::default());
+        assert_eq!((standard.flush, standard.nobs), (0, 0));
+        let rules = Rules {
+            crib_flush: CribFlush::FourCard,
+            ..Rules::default()
+        };
+        assert_eq!(
+            show_score_with_rules(&crib, &starter, true, &rules).flush,
+            4
+        );
+
+        let starter = Card::new(Suit::Hearts, Rank::Nine);
+        let rules = Rules {
+            nobs: false,
+            ..Rules::default()
+        };
+        assert_eq!(
+            show_score_with_rules(&crib, &starter, false, &rules).nobs,
+            0
+        );
+        assert_eq!(
+            show_score_with_rules(&crib, &starter, false, &Rules::default()).nobs,
+            1
+        );
+    }
 
     #[test]
     fn it_counts_fifteens() {
@@ -260,7 +500,7 @@ mod tests {
     }
 
     #[test]
-    fn it_counts_a_nineteen_point_hand() {
+    fn it_counts_a_nineteen_hand_as_zero() {
         let hand = vec![
             Card::new(Suit::Hearts, Rank::Two),
             Card::new(Suit::Clubs, Rank::Four),
@@ -271,4 +511,188 @@ mod tests {
         let score = score_the_show(&hand, &starter);
         assert_eq!(score, 0);
     }
+
+    #[test]
+    fn it_only_counts_five_card_flushes_in_the_crib() {
+        let crib = vec![
+            Card::new(Suit::Spades, Rank::Queen),
+            Card::new(Suit::Spades, Rank::Ten),
+            Card::new(Suit::Spades, Rank::Eight),
+            Card::new(Suit::Spades, Rank::Six),
+        ];
+        assert_eq!(
+            score_the_crib(&crib, &Card::new(Suit::Clubs, Rank::Four)),
+            0
+        );
+        assert_eq!(
+            score_the_crib(&crib, &Card::new(Suit::Spades, Rank::Four)),
+            5
+        );
+    }
+
+    #[test]
+    fn it_counts_three_card_hands() {
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Four),
+            Card::new(Suit::Hearts, Rank::Six),
+        ];
+        assert_eq!(
+            score_the_show(&hand, &Card::new(Suit::Clubs, Rank::King)),
+            3
+        );
+        assert_eq!(
+            score_the_show(&hand, &Card::new(Suit::Hearts, Rank::Eight)),
+            4
+        );
+
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Diamonds, Rank::Five),
+            Card::new(Suit::Spades, Rank::Five),
+        ];
+        let score = show_score(&hand, &Card::new(Suit::Clubs, Rank::Jack), false);
+        assert_eq!((score.fifteens, score.pairs), (8, 6));
+    }
+
+    #[test]
+    fn it_counts_five_card_hands() {
+        let hand = vec![
+            Card::new(Suit::Spades, Rank::Four),
+            Card::new(Suit::Diamonds, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Eight),
+            Card::new(Suit::Clubs, Rank::Two),
+        ];
+        let score = show_score(&hand, &Card::new(Suit::Clubs, Rank::Five), false);
+        assert_eq!((score.fifteens, score.runs, score.total()), (8, 5, 13));
+
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Four),
+            Card::new(Suit::Hearts, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Eight),
+            Card::new(Suit::Hearts, Rank::Queen),
+        ];
+        let starter = Card::new(Suit::Hearts, Rank::King);
+        assert_eq!(show_score(&hand, &starter, false).flush, 6);
+        assert_eq!(
+            show_score(&hand, &Card::new(Suit::Clubs, Rank::King), false).flush,
+            5
+        );
+    }
+
+    #[test]
+    fn it_breaks_down_the_score() {
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Jack),
+            Card::new(Suit::Spades, Rank::Five),
+            Card::new(Suit::Diamonds, Rank::Five),
+            Card::new(Suit::Clubs, Rank::Five),
+        ];
+        let starter = Card::new(Suit::Hearts, Rank::Five);
+        let score = show_score(&hand, &starter, false);
+        assert_eq!(
+            score,
+            ShowScore {
+                fifteens: 16,
+                pairs: 12,
+                runs: 0,
+                flush: 0,
+                nobs: 1,
+            }
+        );
+        assert_eq!(score.total(), 29);
+    }
+
+    #[test]
+    fn it_counts_jokers_as_the_best_card() {
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Diamonds, Rank::Five),
+            Card::new(Suit::Spades, Rank::Five),
+            JOKERS[0],
+        ];
+        let starter = Card::new(Suit::Clubs, Rank::Jack);
+        let wild = wild_show_score(&hand, &starter, false, &Rules::default());
+        assert_eq!(wild.score.total(), 28);
+        assert_eq!(
+            wild.substitutions,
+            vec![(JOKERS[0], Card::new(Suit::Clubs, Rank::Five))]
+        );
+
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Diamonds, Rank::Five),
+            JOKERS[0],
+            JOKERS[1],
+        ];
+        let starter = Card::new(Suit::Spades, Rank::Five);
+        let wild = wild_show_score(&hand, &starter, false, &Rules::default());
+        assert_eq!(wild.score.total(), 29);
+        assert_eq!(
+            wild.substitutions,
+            vec![
+                (JOKERS[0], Card::new(Suit::Clubs, Rank::Five)),
+                (JOKERS[1], Card::new(Suit::Spades, Rank::Jack)),
+            ]
+        );
+
+        let hand = vec![
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Diamonds, Rank::Five),
+            Card::new(Suit::Spades, Rank::Five),
+            Card::new(Suit::Clubs, Rank::Jack),
+        ];
+        assert_eq!(show_score(&hand, &JOKERS[1], false).total(), 29);
+        let rules = Rules {
+            nobs: false,
+            ..Rules::default()
+        };
+        assert_eq!(
+            show_score_with_rules(&hand, &JOKERS[1], false, &rules).total(),
+            28
+        );
+    }
+
+    #[test]
+    fn it_scores_card_sets_like_the_show() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(31);
+        let mut deck = CardSet::FULL.iter().collect_vec();
+        for _ in 0..20_000 {
+            deck.shuffle(&mut rng);
+            let (hand, starter) = (&deck[..4], &deck[4]);
+            let set = hand.iter().collect();
+            assert_eq!(
+                show_score_set(set, *starter, false).unwrap(),
+                show_score(hand, starter, false)
+            );
+            assert_eq!(
+                show_score_set(set, *starter, true).unwrap(),
+                show_score(hand, starter, true)
+            );
+        }
+        let hand: CardSet = deck[..4].iter().collect();
+        assert_eq!(show_score_set(hand, JOKERS[0], false), None);
+    }
+
+    #[test]
+    #[ignore = "scores all 13 million hands, run with --release --ignored"]
+    fn it_scores_every_card_set_like_the_show() {
+        for hand in CardSet::FULL.iter().combinations(4) {
+            let set: CardSet = hand.iter().collect();
+            for starter in set.complement() {
+                assert_eq!(
+                    show_score_set(set, starter, false).unwrap(),
+                    show_score(&hand, &starter, false),
+                );
+                assert_eq!(
+                    show_score_set(set, starter, true).unwrap(),
+                    show_score(&hand, &starter, true),
+                );
+            }
+        }
+    }
 }
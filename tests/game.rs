@@ -0,0 +1,746 @@
+use cribbage::{
+    card::{Card, Deck},
+    game::{Game, GameAction, GameEvent, ScoreReason},
+    rules::{CribFlush, Rules},
+    scripted::{launch_scripted, Step},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{sync::mpsc::sync_channel, thread, time::Duration};
+
+const DEALER: usize = 0;
+const PONE: usize = 1;
+/// Left of the pone, and the dealer's partner in a four player game
+const THIRD: usize = 2;
+/// The pone's partner in a four player game
+const FOURTH: usize = 3;
+
+fn card(s: &str) -> Card {
+    s.parse().unwrap()
+}
+
+fn cards(s: &str) -> Vec<Card> {
+    s.split_whitespace().map(card).collect()
+}
+
+/// Shuffles the rest of a stacked deck the same way every run
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+/// Plays a game between two scripted players where `DEALER` deals first from `decks`, and
+/// returns the final scores with the events each player received
+fn play(decks: Vec<Deck>, scripts: [Vec<Step>; 2]) -> (Vec<u16>, [Vec<GameEvent>; 2]) {
+    play_with_rules(Rules::default(), decks, scripts)
+}
+
+/// Like `play`, for as many scripted players as `rules` seat
+fn play_with_rules<const N: usize>(
+    rules: Rules,
+    decks: Vec<Deck>,
+    scripts: [Vec<Step>; N],
+) -> (Vec<u16>, [Vec<GameEvent>; N]) {
+    let mut game = Game::with_seed(rules, 0);
+    game.set_show_delay(Duration::ZERO);
+    game.set_first_dealer(DEALER);
+    for deck in decks {
+        game.stack_deck(deck);
+    }
+
+    let ids = ["Dealer", "Pone", "Third", "Fourth"];
+    let mut i = 0;
+    let handles = scripts.map(|script| {
+        let (event_sender, event_receiver) = sync_channel(1);
+        let action_sender = game.register_player(ids[i].to_string(), event_sender);
+        i += 1;
+        thread::spawn(move || launch_scripted(script, event_receiver, action_sender))
+    });
+    game.start();
+    let scores = game.scores();
+    drop(game);
+    (scores, handles.map(|handle| handle.join().unwrap()))
+}
+
+fn scored(player: usize, points: i16, reason: ScoreReason, total: u16) -> GameEvent {
+    GameEvent::Scored {
+        player,
+        points,
+        reason,
+        total,
+    }
+}
+
+#[test]
+fn it_scores_a_stacked_hand() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S JC 9D 9H"),
+        &cards("4S 6D 7H 8C KS QS"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Expect(GameEvent::Starter { card: card("5C") }),
+        Step::Play(card("5H")),
+        Step::Play(card("5D")),
+        Step::Expect(scored(PONE, 1, ScoreReason::Go, 9)),
+        Step::Play(card("5S")),
+        Step::Play(card("JC")),
+        Step::Expect(GameEvent::Show {
+            player: DEALER,
+            cards: cards("5H 5D 5S JC"),
+            starter: card("5C"),
+            crib: false,
+            score: 29,
+            substitutions: vec![],
+        }),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Play(card("6D")),
+        Step::Expect(scored(PONE, 5, ScoreReason::Play, 5)),
+        Step::Play(card("7H")),
+        Step::Play(card("8C")),
+    ];
+
+    let (scores, [events, _]) = play(vec![deck], [dealer, pone]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(PONE, 5, ScoreReason::Play, 5),
+            scored(PONE, 3, ScoreReason::Play, 8),
+            scored(PONE, 1, ScoreReason::Go, 9),
+            scored(DEALER, 1, ScoreReason::LastCard, 1),
+            scored(PONE, 9, ScoreReason::Hand, 18),
+            scored(DEALER, 29, ScoreReason::Hand, 30),
+            scored(DEALER, 6, ScoreReason::Crib, 36),
+        ]
+    );
+    assert_eq!(scores, vec![36, 18]);
+}
+
+#[test]
+fn it_plays_the_five_card_game() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S 9D 9H"),
+        &cards("4S 6D 7H KS QS"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Play(card("5H")),
+        Step::Play(card("5D")),
+        Step::Play(card("5S")),
+        Step::Expect(GameEvent::Show {
+            player: DEALER,
+            cards: cards("5H 5D 5S"),
+            starter: card("5C"),
+            crib: false,
+            score: 20,
+            substitutions: vec![],
+        }),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Play(card("6D")),
+        Step::Play(card("7H")),
+    ];
+
+    let (scores, [events, _]) = play_with_rules(Rules::five_card(), vec![deck], [dealer, pone]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(PONE, 3, ScoreReason::PoneBonus, 3),
+            scored(PONE, 5, ScoreReason::Play, 8),
+            scored(PONE, 3, ScoreReason::Play, 11),
+            scored(PONE, 1, ScoreReason::Go, 12),
+            scored(DEALER, 1, ScoreReason::LastCard, 1),
+            scored(PONE, 6, ScoreReason::Hand, 18),
+            scored(DEALER, 20, ScoreReason::Hand, 21),
+            scored(DEALER, 6, ScoreReason::Crib, 27),
+        ]
+    );
+    assert_eq!(scores, vec![27, 18]);
+}
+
+#[test]
+fn it_plays_the_seven_card_game() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S JC AD 9D 9H"),
+        &cards("4S 6D 7H 8C 2C KS QS"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Play(card("5H")),
+        Step::Play(card("5D")),
+        Step::Play(card("AD")),
+        Step::Play(card("5S")),
+        Step::Play(card("JC")),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Play(card("6D")),
+        Step::Play(card("7H")),
+        Step::Play(card("2C")),
+        Step::Play(card("8C")),
+    ];
+
+    let (_, [events, _]) = play_with_rules(Rules::seven_card(), vec![deck], [dealer, pone]);
+
+    let shown = events
+        .into_iter()
+        .filter_map(|event| match event {
+            GameEvent::Show { cards, score, .. } => Some((cards.len(), score)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(shown, vec![(5, 13), (5, 29), (4, 6)]);
+}
+
+#[test]
+fn it_plays_three_handed() {
+    // each player is dealt five, then the crib gets one from the deck
+    let deck = Deck::stacked_deal(
+        &cards("KS QD 5H 2C 9C KH QH 8D 3D 7C KD JD 9S 4S 6C AS"),
+        card("7H"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("6C")),
+        Step::Play(card("KD")),
+        Step::Play(card("JD")),
+        Step::Play(card("9S")),
+        Step::Play(card("4S")),
+        Step::Expect(GameEvent::Show {
+            player: DEALER,
+            cards: cards("AS 6C 9C 7C"),
+            starter: card("7H"),
+            crib: true,
+            score: 6,
+            substitutions: vec![],
+        }),
+    ];
+    let pone = vec![
+        Step::Discard(cards("9C")),
+        Step::Cut(4),
+        Step::Play(card("KS")),
+        Step::Play(card("QD")),
+        Step::Play(card("5H")),
+        Step::Play(card("2C")),
+    ];
+    let third = vec![
+        Step::Discard(cards("7C")),
+        Step::Play(card("KH")),
+        Step::Play(card("QH")),
+        Step::Play(card("8D")),
+        Step::Play(card("3D")),
+    ];
+
+    let (scores, [events, _, _]) =
+        play_with_rules(Rules::three_player(), vec![deck], [dealer, pone, third]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(THIRD, 2, ScoreReason::Play, 2),
+            scored(DEALER, 6, ScoreReason::Play, 6),
+            // the go passes the pone and third player back to the dealer
+            scored(DEALER, 1, ScoreReason::Go, 7),
+            scored(THIRD, 2, ScoreReason::Play, 4),
+            scored(DEALER, 1, ScoreReason::Go, 8),
+            scored(DEALER, 3, ScoreReason::Play, 11),
+            scored(DEALER, 2, ScoreReason::ThirtyOne, 13),
+            scored(PONE, 4, ScoreReason::Hand, 4),
+            scored(THIRD, 2, ScoreReason::Hand, 6),
+            scored(DEALER, 6, ScoreReason::Crib, 19),
+        ]
+    );
+    assert_eq!(scores, vec![19, 4, 6]);
+}
+
+#[test]
+fn it_pools_partners_scores() {
+    let deck = Deck::stacked_deal(
+        &cards("KS QD 5H 2C 9C KH QH 8D 3D 7C KC JC 6S 4H 2D KD JD 9S 4S 6C"),
+        card("AH"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Expect(GameEvent::Seat {
+            seat: DEALER,
+            partner: Some(THIRD),
+            rules: Rules::four_player(),
+        }),
+        Step::Discard(cards("6C")),
+        Step::Play(card("KD")),
+        Step::Play(card("JD")),
+        Step::Play(card("9S")),
+        Step::Play(card("4S")),
+    ];
+    let pone = vec![
+        Step::Discard(cards("9C")),
+        Step::Cut(4),
+        Step::Play(card("KS")),
+        Step::Play(card("QD")),
+        Step::Play(card("5H")),
+        Step::Play(card("2C")),
+    ];
+    let third = vec![
+        Step::Discard(cards("7C")),
+        Step::Play(card("KH")),
+        Step::Play(card("QH")),
+        Step::Play(card("3D")),
+        Step::Play(card("8D")),
+    ];
+    let fourth = vec![
+        Step::Discard(cards("2D")),
+        Step::Play(card("KC")),
+        Step::Play(card("JC")),
+        Step::Play(card("6S")),
+        Step::Play(card("4H")),
+    ];
+
+    let (scores, [events, _, _, _]) = play_with_rules(
+        Rules::four_player(),
+        vec![deck],
+        [dealer, pone, third, fourth],
+    );
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(THIRD, 2, ScoreReason::Play, 2),
+            scored(FOURTH, 6, ScoreReason::Play, 6),
+            // everyone else has said go when it comes back around
+            scored(FOURTH, 1, ScoreReason::Go, 7),
+            scored(THIRD, 2, ScoreReason::Play, 4),
+            scored(THIRD, 1, ScoreReason::Go, 5),
+            scored(DEALER, 2, ScoreReason::Play, 7),
+            scored(PONE, 1, ScoreReason::Go, 8),
+            scored(DEALER, 2, ScoreReason::Play, 9),
+            scored(DEALER, 2, ScoreReason::ThirtyOne, 11),
+            scored(PONE, 4, ScoreReason::Hand, 12),
+            scored(FOURTH, 4, ScoreReason::Hand, 16),
+            scored(DEALER, 4, ScoreReason::Hand, 15),
+            scored(DEALER, 4, ScoreReason::Crib, 19),
+        ]
+    );
+    assert_eq!(scores, vec![19, 16, 19, 16]);
+}
+
+#[test]
+fn it_lets_the_opponent_take_missed_points_with_muggins() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S JC 9D 9H"),
+        &cards("4S 6D 7H 8C KS QS"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    // each play and show is claimed, then put to the opponent
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Claim(0),
+        Step::Play(card("5H")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("5D")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("5S")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("JC")),
+        Step::Claim(0),
+        // the pone's hand is worth 9
+        Step::Claim(3),
+        Step::Claim(29),
+        // the crib is only worth 6
+        Step::Claim(8),
+        Step::Expect(GameEvent::ClaimRejected {
+            player: DEALER,
+            claimed: 8,
+        }),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("6D")),
+        Step::Claim(5),
+        Step::Claim(0),
+        Step::Play(card("7H")),
+        Step::Claim(3),
+        Step::Claim(0),
+        Step::Play(card("8C")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Claim(6),
+        Step::Claim(0),
+    ];
+    let rules = Rules {
+        muggins: true,
+        ..Rules::default()
+    };
+
+    let (scores, [events, _]) = play_with_rules(rules, vec![deck], [dealer, pone]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(PONE, 5, ScoreReason::Play, 5),
+            scored(PONE, 3, ScoreReason::Play, 8),
+            scored(PONE, 1, ScoreReason::Go, 9),
+            scored(DEALER, 1, ScoreReason::LastCard, 1),
+            scored(PONE, 6, ScoreReason::Hand, 15),
+            scored(DEALER, 3, ScoreReason::Muggins, 4),
+            scored(DEALER, 29, ScoreReason::Hand, 33),
+        ]
+    );
+    assert_eq!(scores, vec![33, 15]);
+}
+
+#[test]
+fn it_checks_muggins_claims_by_the_house_rules() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S JC 2D 9D"),
+        &cards("4S 6D 7H 8C KD QD"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("2D 9D")),
+        Step::Claim(0),
+        Step::Play(card("5H")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("5D")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("5S")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("JC")),
+        Step::Claim(0),
+        Step::Claim(0),
+        // without nobs the hand is only worth 28
+        Step::Claim(29),
+        Step::Expect(GameEvent::ClaimRejected {
+            player: DEALER,
+            claimed: 29,
+        }),
+        // a four card flush counts in the crib
+        Step::Claim(8),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KD QD")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Play(card("6D")),
+        Step::Claim(5),
+        Step::Claim(0),
+        Step::Play(card("7H")),
+        Step::Claim(3),
+        Step::Claim(0),
+        Step::Play(card("8C")),
+        Step::Claim(0),
+        Step::Claim(0),
+        Step::Claim(9),
+        Step::Claim(0),
+    ];
+    let rules = Rules {
+        muggins: true,
+        nobs: false,
+        crib_flush: CribFlush::FourCard,
+        ..Rules::default()
+    };
+
+    let (scores, [events, _]) = play_with_rules(rules, vec![deck], [dealer, pone]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| matches!(event, GameEvent::Scored { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(PONE, 5, ScoreReason::Play, 5),
+            scored(PONE, 3, ScoreReason::Play, 8),
+            scored(PONE, 1, ScoreReason::Go, 9),
+            scored(DEALER, 1, ScoreReason::LastCard, 1),
+            scored(PONE, 9, ScoreReason::Hand, 18),
+            scored(DEALER, 8, ScoreReason::Crib, 9),
+        ]
+    );
+    assert_eq!(scores, vec![9, 18]);
+}
+
+#[test]
+fn it_makes_the_first_out_lose_at_lowball() {
+    let deck = Deck::stacked(
+        &cards("5H 5D 5S JC 9D 9H"),
+        &cards("4S 6D 7H 8C KS QS"),
+        card("5C"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Play(card("5H")),
+        Step::Play(card("5D")),
+        Step::Play(card("5S")),
+        Step::Play(card("JC")),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Play(card("6D")),
+        Step::Play(card("7H")),
+        Step::Play(card("8C")),
+        Step::Expect(GameEvent::GameOver {
+            winner: PONE,
+            scores: vec![30, 18],
+        }),
+    ];
+    // the dealer's 29 hand takes them out
+    let rules = Rules {
+        target_score: 30,
+        skunk_line: 0,
+        double_skunk_line: 0,
+        ..Rules::lowball()
+    };
+
+    let (scores, _) = play_with_rules(rules, vec![deck], [dealer, pone]);
+
+    assert_eq!(scores, vec![30, 18]);
+}
+
+#[test]
+fn it_backs_up_a_handicapped_player_who_sets_up_31() {
+    let deck = Deck::stacked(
+        &cards("KH QD 6C 2S 9D 9H"),
+        &cards("KS 5H 3D AC 7C 8C"),
+        card("4H"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Play(card("KH")),
+        Step::Play(card("6C")),
+        Step::Play(card("QD")),
+        Step::Play(card("2S")),
+    ];
+    let pone = vec![
+        Step::Discard(cards("7C 8C")),
+        Step::Cut(4),
+        Step::Play(card("KS")),
+        Step::Play(card("5H")),
+        Step::Play(card("3D")),
+        Step::Play(card("AC")),
+    ];
+    let rules = Rules {
+        handicap: vec![0, 20],
+        back_up: 10,
+        ..Rules::default()
+    };
+
+    let (_, [events, _]) = play_with_rules(rules, vec![deck], [dealer, pone]);
+
+    let pegged = events
+        .into_iter()
+        .filter(|event| {
+            matches!(event, GameEvent::Scored { reason, .. }
+                if !matches!(reason, ScoreReason::Hand | ScoreReason::Crib))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pegged,
+        vec![
+            scored(DEALER, 2, ScoreReason::Play, 2),
+            scored(DEALER, 2, ScoreReason::ThirtyOne, 4),
+            scored(PONE, -10, ScoreReason::BackUp, 10),
+            scored(DEALER, 1, ScoreReason::LastCard, 5),
+        ]
+    );
+}
+
+#[test]
+fn it_counts_a_joker_as_the_best_card_in_the_show() {
+    let deck = Deck::shuffled_with_jokers(Rules::jokers().jokers, &mut rng())
+        .stack(&cards("4S 6D 7H 8C KS QS 5H 5D 5S BJ 9D 9H"), card("5C"))
+        .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("9D 9H")),
+        Step::Play(card("5H")),
+        Step::Play(card("5D")),
+        Step::Play(card("BJ")),
+        Step::Play(card("5S")),
+        Step::Expect(GameEvent::Show {
+            player: DEALER,
+            cards: cards("5H 5D 5S BJ"),
+            starter: card("5C"),
+            crib: false,
+            score: 29,
+            substitutions: vec![(card("BJ"), card("JC"))],
+        }),
+    ];
+    let pone = vec![
+        Step::Discard(cards("KS QS")),
+        Step::Cut(4),
+        Step::Play(card("4S")),
+        Step::Play(card("6D")),
+        Step::Play(card("7H")),
+        Step::Play(card("8C")),
+    ];
+
+    let (scores, [events, _]) = play_with_rules(Rules::jokers(), vec![deck], [dealer, pone]);
+
+    // counting nothing, the joker can be played at 27 to take the go
+    assert!(events.contains(&GameEvent::Played {
+        player: DEALER,
+        card: card("BJ"),
+        count: 27,
+    }));
+    assert!(events.contains(&scored(DEALER, 1, ScoreReason::Go, 1)));
+    assert_eq!(scores, vec![37, 17]);
+}
+
+#[test]
+fn it_gives_his_heels_to_the_dealer() {
+    let deck = Deck::stacked(
+        &cards("AH 2H 3H 4H 5H 6H"),
+        &cards("AS 2S 3S 4S 5S 6S"),
+        card("JD"),
+        &mut rng(),
+    )
+    .unwrap();
+    let dealer = vec![
+        Step::Discard(cards("5H 6H")),
+        Step::Expect(scored(DEALER, 2, ScoreReason::Heels, 2)),
+        Step::Play(card("AH")),
+        Step::Play(card("2H")),
+        Step::Play(card("3H")),
+        Step::Play(card("4H")),
+    ];
+    let pone = vec![
+        Step::Discard(cards("5S 6S")),
+        Step::Cut(4),
+        Step::Play(card("AS")),
+        Step::Play(card("2S")),
+        Step::Play(card("3S")),
+        Step::Play(card("4S")),
+    ];
+
+    let (_, [_, events]) = play(vec![deck], [dealer, pone]);
+
+    let first_score = events
+        .iter()
+        .find(|event| matches!(event, GameEvent::Scored { .. }));
+    assert_eq!(first_score, Some(&scored(DEALER, 2, ScoreReason::Heels, 2)));
+}
+
+#[test]
+fn it_cuts_for_the_first_deal() {
+    let mut ties = 0;
+    for seed in 0..32 {
+        let mut game = Game::with_seed(Rules::default(), seed);
+        let handles = [10, 20].map(|position| {
+            let (event_sender, event_receiver) = sync_channel(1);
+            let action_sender = game.register_player(position.to_string(), event_sender);
+            // cut as often as a tie asks, and leave once the first hand is dealt
+            thread::spawn(move || {
+                let mut events = Vec::new();
+                while let Ok(event) = event_receiver.recv() {
+                    if let GameEvent::CutRequest { .. } = event {
+                        action_sender.send(GameAction::Cut { position }).unwrap();
+                    }
+                    let dealt = matches!(event, GameEvent::Deal { .. });
+                    events.push(event);
+                    if dealt {
+                        break;
+                    }
+                }
+                events
+            })
+        });
+        game.start();
+        drop(game);
+        let [events, _] = handles.map(|handle| handle.join().unwrap());
+
+        let cuts = events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::CutForDeal { player, card } => Some((*player, *card)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let dealer = events.iter().find_map(|event| match event {
+            GameEvent::Deal { dealer, .. } => Some(if *dealer { 0 } else { 1 }),
+            _ => None,
+        });
+        // every round but the last is a tie, and the low card of the last round deals
+        let rounds = cuts.chunks(2).collect::<Vec<_>>();
+        let Some((last, tied)) = rounds.split_last() else {
+            panic!("expected a round of cuts, seed {}", seed);
+        };
+        for round in tied {
+            assert_eq!(
+                round[0].1.run_order(),
+                round[1].1.run_order(),
+                "seed {}",
+                seed
+            );
+        }
+        ties += tied.len();
+        let [(first, a), (second, b)] = last[..] else {
+            panic!("expected a cut from each player, got {:?}", last);
+        };
+        assert_eq!((first, second), (0, 1));
+        assert_ne!(a.run_order(), b.run_order(), "seed {}", seed);
+        let low = if a.run_order() < b.run_order() { 0 } else { 1 };
+        assert_eq!(dealer, Some(low), "seed {}", seed);
+    }
+    assert!(ties > 0, "no seed tied the cut");
+}